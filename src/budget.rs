@@ -0,0 +1,237 @@
+//! Size budgets for the SKILL.md body and the Markdown files it references.
+//!
+//! An agent loads the full SKILL.md body as soon as a skill triggers, while
+//! referenced files are only read on demand. Long bodies should therefore move
+//! detail into `references/`, and long reference files should open with a
+//! table of contents so an agent can jump to the part it needs.
+
+use std::fs;
+use std::path::Path;
+
+use serde::Deserialize;
+
+use crate::error::ValidationError;
+use crate::references::{referenced_markdown_files, relative_display};
+use crate::tokens::estimate_tokens;
+
+/// Minimum number of `](#anchor)` list entries that count as a table of contents.
+const MIN_TOC_ENTRIES: usize = 3;
+
+/// Size budget for the SKILL.md body.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct BodyBudget {
+    /// Maximum number of lines in the body. `None` disables the limit.
+    pub max_lines: Option<usize>,
+    /// Maximum number of characters in the body. `None` disables the limit.
+    pub max_chars: Option<usize>,
+    /// Maximum estimated number of tokens in the body. `None` disables the limit.
+    pub max_tokens: Option<usize>,
+    /// Referenced Markdown files longer than this many lines need a table of contents.
+    pub toc_min_lines: usize,
+}
+
+impl Default for BodyBudget {
+    fn default() -> Self {
+        Self {
+            max_lines: Some(500),
+            max_chars: None,
+            max_tokens: Some(5000),
+            toc_min_lines: 100,
+        }
+    }
+}
+
+/// Token budget for the `description` field.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DescriptionBudget {
+    /// Maximum estimated number of tokens in the description. `None` disables the limit.
+    pub max_tokens: Option<usize>,
+}
+
+/// Check the SKILL.md body against the line, character and token budgets.
+pub fn check_body_budget(body: &str, budget: &BodyBudget) -> Vec<ValidationError> {
+    let measures = [
+        ("lines", budget.max_lines, body.lines().count()),
+        ("characters", budget.max_chars, body.chars().count()),
+    ];
+
+    let mut errors: Vec<ValidationError> = measures
+        .into_iter()
+        .filter_map(|(unit, limit, actual)| {
+            let limit = limit?;
+            (actual > limit).then_some(ValidationError::BodyTooLong {
+                unit,
+                limit,
+                actual,
+            })
+        })
+        .collect();
+
+    if let Some(limit) = budget.max_tokens {
+        let actual = estimate_tokens(body);
+        if actual > limit {
+            errors.push(ValidationError::BodyTokenBudget { limit, actual });
+        }
+    }
+
+    errors
+}
+
+/// Check a description against its token budget.
+pub fn check_description_budget(
+    description: &str,
+    budget: &DescriptionBudget,
+) -> Option<ValidationError> {
+    let limit = budget.max_tokens?;
+    let actual = estimate_tokens(description);
+    (actual > limit).then_some(ValidationError::DescriptionTokenBudget { limit, actual })
+}
+
+/// Report referenced Markdown files that are long but have no table of contents.
+pub fn check_reference_toc(
+    skill_dir: &Path,
+    body: &str,
+    budget: &BodyBudget,
+) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for path in referenced_markdown_files(skill_dir, body) {
+        let Ok(content) = fs::read_to_string(&path) else {
+            continue;
+        };
+        let lines = content.lines().count();
+        if lines <= budget.toc_min_lines || has_table_of_contents(&content) {
+            continue;
+        }
+        let rel = path.strip_prefix(skill_dir).unwrap_or(&path);
+        errors.push(ValidationError::ReferenceMissingToc {
+            path: relative_display(rel),
+            lines,
+        });
+    }
+    errors
+}
+
+/// Whether Markdown has a "Contents" heading or a list of in-page anchor links.
+fn has_table_of_contents(markdown: &str) -> bool {
+    let mut anchor_entries = 0;
+    for line in markdown.lines() {
+        let line = line.trim();
+        if let Some(heading) = line.strip_prefix('#') {
+            let heading = heading.trim_start_matches('#').trim().to_lowercase();
+            if heading.contains("contents") || heading == "toc" {
+                return true;
+            }
+        }
+        let is_list_item = line.starts_with("- ")
+            || line.starts_with("* ")
+            || line
+                .split_once(". ")
+                .is_some_and(|(number, _)| number.chars().all(|c| c.is_ascii_digit()));
+        if is_list_item && line.contains("](#") {
+            anchor_entries += 1;
+            if anchor_entries >= MIN_TOC_ENTRIES {
+                return true;
+            }
+        }
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn body_within_budget_passes() {
+        assert!(check_body_budget("# Title\n\nShort body.", &BodyBudget::default()).is_empty());
+    }
+
+    #[test]
+    fn body_over_budget_reports_each_measure() {
+        let budget = BodyBudget {
+            max_lines: Some(2),
+            max_chars: Some(10),
+            max_tokens: Some(4),
+            toc_min_lines: 100,
+        };
+        let errors = check_body_budget("one\ntwo\nthree", &budget);
+        assert_eq!(
+            errors,
+            [
+                ValidationError::BodyTooLong {
+                    unit: "lines",
+                    limit: 2,
+                    actual: 3
+                },
+                ValidationError::BodyTooLong {
+                    unit: "characters",
+                    limit: 10,
+                    actual: 13
+                },
+                ValidationError::BodyTokenBudget {
+                    limit: 4,
+                    actual: 5
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn description_token_budget_is_opt_in() {
+        let description = "Extract text and tables from PDF files";
+        assert_eq!(
+            check_description_budget(description, &DescriptionBudget::default()),
+            None
+        );
+        let budget = DescriptionBudget {
+            max_tokens: Some(5),
+        };
+        assert_eq!(
+            check_description_budget(description, &budget),
+            Some(ValidationError::DescriptionTokenBudget {
+                limit: 5,
+                actual: 8
+            })
+        );
+    }
+
+    #[test]
+    fn detects_table_of_contents() {
+        assert!(has_table_of_contents("# Guide\n\n## Table of Contents\n"));
+        assert!(has_table_of_contents("- [A](#a)\n- [B](#b)\n1. [C](#c)\n"));
+        assert!(!has_table_of_contents("# Guide\n\n- [A](#a)\n\n## A\n"));
+    }
+
+    #[test]
+    fn long_reference_without_toc_is_reported() {
+        let dir = TempDir::new().expect("temp dir");
+        let references = dir.path().join("references");
+        fs::create_dir_all(&references).expect("mkdir");
+        let long = "line\n".repeat(20);
+        fs::write(references.join("long.md"), &long).expect("write");
+        fs::write(
+            references.join("indexed.md"),
+            format!("## Contents\n{long}"),
+        )
+        .expect("write");
+        fs::write(references.join("short.md"), "line\n").expect("write");
+
+        let budget = BodyBudget {
+            toc_min_lines: 10,
+            ..BodyBudget::default()
+        };
+        let body = "See [long](references/long.md), [indexed](references/indexed.md) \
+                    and [short](references/short.md).";
+        assert_eq!(
+            check_reference_toc(dir.path(), body, &budget),
+            [ValidationError::ReferenceMissingToc {
+                path: "references/long.md".to_string(),
+                lines: 20
+            }]
+        );
+    }
+}
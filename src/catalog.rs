@@ -0,0 +1,453 @@
+//! Checks that span every skill in a repository.
+//!
+//! An agent loads the `description` of every installed skill into its context
+//! at startup, so descriptions that are fine one by one can still add up to
+//! more than a team can afford. [`check_catalog_budget`] sums them over a
+//! catalog of skills and reports the largest contributors.
+//!
+//! Agents also pick silently between skills that share a name, so
+//! [`find_duplicate_names`] reports names that collide anywhere in the catalog,
+//! and the opt-in [`find_overlapping_descriptions`] reports skills whose
+//! descriptions are similar enough to compete for the same requests.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::Deserialize;
+use serde_yaml::Value;
+use unicode_normalization::UnicodeNormalization;
+use unicode_security::confusable_detection::skeleton;
+
+use crate::discovery::{display_path, get_dir_name};
+use crate::error::ValidationError;
+use crate::formatting::parse_frontmatter;
+use crate::skill::SkillFile;
+use crate::tokens::estimate_tokens;
+
+/// Words ignored when comparing descriptions.
+const STOP_WORDS: [&str; 40] = [
+    "a", "an", "and", "any", "are", "as", "at", "be", "by", "can", "do", "for", "from", "how",
+    "in", "into", "is", "it", "its", "of", "on", "or", "that", "the", "their", "them", "this",
+    "to", "use", "used", "user", "uses", "using", "when", "whenever", "with", "you", "your",
+    "skill", "skills",
+];
+
+/// Catalog-wide limits on skill descriptions.
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct CatalogBudget {
+    /// Maximum total description length in characters. `None` disables the limit.
+    pub max_description_chars: Option<usize>,
+    /// Maximum total estimated description tokens. `None` disables the limit.
+    pub max_description_tokens: Option<usize>,
+    /// Number of largest contributors listed when a budget is exceeded.
+    pub top: usize,
+    /// Report pairs of descriptions at least this similar (0.0 to 1.0). `None` disables the check.
+    pub similarity_threshold: Option<f64>,
+}
+
+impl Default for CatalogBudget {
+    fn default() -> Self {
+        Self {
+            max_description_chars: None,
+            max_description_tokens: None,
+            top: 5,
+            similarity_threshold: None,
+        }
+    }
+}
+
+impl CatalogBudget {
+    /// Whether any catalog limit is set.
+    pub const fn is_enabled(&self) -> bool {
+        self.max_description_chars.is_some() || self.max_description_tokens.is_some()
+    }
+}
+
+/// Size of one skill's description.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DescriptionSize {
+    /// The skill name, or its directory name if `name` is missing.
+    pub skill: String,
+    /// Length in characters.
+    pub chars: usize,
+    /// Estimated number of tokens.
+    pub tokens: usize,
+}
+
+/// Measure the description of every skill that has one.
+///
+/// Skills whose frontmatter cannot be parsed are skipped; they are reported by
+/// the per-skill checks.
+pub fn description_sizes(skills: &[SkillFile]) -> Vec<DescriptionSize> {
+    skills
+        .iter()
+        .filter_map(|skill| {
+            let (metadata, _) = parse_frontmatter(&skill.content).ok()?;
+            let Some(Value::String(description)) = metadata.get("description") else {
+                return None;
+            };
+            Some(DescriptionSize {
+                skill: skill_name(&metadata, skill),
+                chars: description.chars().count(),
+                tokens: estimate_tokens(description),
+            })
+        })
+        .collect()
+}
+
+/// Report skills whose names collide across the catalog.
+///
+/// Names are compared after NFKC normalization, ignoring case, hyphens and
+/// underscores, so `pdf-tools`, `PDF_Tools` and `pdftools` conflict. Look-alike
+/// characters are folded too, so a Cyrillic `о` does not make a name distinct. A skill
+/// without a parsable `name` is identified by its directory name. The same
+/// SKILL.md listed twice is counted once. Paths are shown relative to `root`.
+pub fn find_duplicate_names(skills: &[SkillFile], root: &Path) -> Vec<ValidationError> {
+    let mut groups: BTreeMap<String, Vec<(String, &SkillFile)>> = BTreeMap::new();
+    for skill in skills {
+        let name = parse_frontmatter(&skill.content).map_or_else(
+            |_| get_dir_name(&skill.dir_path),
+            |(metadata, _)| skill_name(&metadata, skill),
+        );
+        let group = groups.entry(name_key(&name)).or_default();
+        let canonical = skill.file_path.canonicalize().ok();
+        if group.iter().any(|(_, other)| {
+            other.file_path == skill.file_path
+                || (canonical.is_some() && other.file_path.canonicalize().ok() == canonical)
+        }) {
+            continue;
+        }
+        group.push((name, skill));
+    }
+
+    groups
+        .into_values()
+        .filter(|group| group.len() > 1)
+        .map(|group| {
+            let mut names: Vec<String> = group.iter().map(|(name, _)| name.clone()).collect();
+            names.dedup();
+            ValidationError::DuplicateSkillName {
+                names,
+                paths: group
+                    .iter()
+                    .map(|(_, skill)| display_path(&skill.dir_path, root))
+                    .collect(),
+            }
+        })
+        .collect()
+}
+
+/// Report pairs of skills whose descriptions are at least `threshold` similar.
+///
+/// Descriptions are compared by the cosine similarity of their TF-IDF word
+/// vectors, computed over the whole catalog so that words shared by many skills
+/// count for less. Words are lowercased, common English and trigger words
+/// ("use", "when", ...) are dropped, and a trailing plural `s` is ignored.
+/// Pairs are returned most similar first.
+pub fn find_overlapping_descriptions(skills: &[SkillFile], threshold: f64) -> Vec<ValidationError> {
+    let documents: Vec<(String, BTreeMap<String, usize>)> = skills
+        .iter()
+        .filter_map(|skill| {
+            let (metadata, _) = parse_frontmatter(&skill.content).ok()?;
+            let Some(Value::String(description)) = metadata.get("description") else {
+                return None;
+            };
+            Some((skill_name(&metadata, skill), term_counts(description)))
+        })
+        .collect();
+
+    let mut document_frequency: BTreeMap<&str, usize> = BTreeMap::new();
+    for (_, terms) in &documents {
+        for term in terms.keys() {
+            *document_frequency.entry(term).or_default() += 1;
+        }
+    }
+    let total = count_as_f64(documents.len());
+    let vectors: Vec<BTreeMap<&str, f64>> = documents
+        .iter()
+        .map(|(_, terms)| {
+            terms
+                .iter()
+                .map(|(term, &count)| {
+                    let df = count_as_f64(document_frequency[term.as_str()]);
+                    let idf = ((1.0 + total) / (1.0 + df)).ln() + 1.0;
+                    (term.as_str(), count_as_f64(count) * idf)
+                })
+                .collect()
+        })
+        .collect();
+
+    let mut pairs: Vec<(f64, usize, usize)> = Vec::new();
+    for i in 0..vectors.len() {
+        for j in i + 1..vectors.len() {
+            let similarity = cosine(&vectors[i], &vectors[j]);
+            if similarity >= threshold {
+                pairs.push((similarity, i, j));
+            }
+        }
+    }
+    pairs.sort_by(|a, b| {
+        b.0.total_cmp(&a.0)
+            .then_with(|| (a.1, a.2).cmp(&(b.1, b.2)))
+    });
+
+    pairs
+        .into_iter()
+        .map(
+            |(similarity, i, j)| ValidationError::OverlappingDescriptions {
+                first: documents[i].0.clone(),
+                second: documents[j].0.clone(),
+                percent: percent(similarity),
+            },
+        )
+        .collect()
+}
+
+fn term_counts(text: &str) -> BTreeMap<String, usize> {
+    let mut counts = BTreeMap::new();
+    for word in text
+        .split(|c: char| !c.is_alphanumeric())
+        .map(str::to_lowercase)
+        .filter(|word| word.chars().count() > 1 && !STOP_WORDS.contains(&word.as_str()))
+    {
+        let term = match word.strip_suffix('s') {
+            Some(stem) if stem.chars().count() > 2 && !stem.ends_with('s') => stem.to_string(),
+            _ => word,
+        };
+        *counts.entry(term).or_default() += 1;
+    }
+    counts
+}
+
+fn cosine(a: &BTreeMap<&str, f64>, b: &BTreeMap<&str, f64>) -> f64 {
+    let dot: f64 = a
+        .iter()
+        .filter_map(|(term, weight)| b.get(term).map(|other| weight * other))
+        .sum();
+    let norm = |vector: &BTreeMap<&str, f64>| vector.values().map(|w| w * w).sum::<f64>().sqrt();
+    let denominator = norm(a) * norm(b);
+    if denominator == 0.0 {
+        0.0
+    } else {
+        dot / denominator
+    }
+}
+
+fn count_as_f64(count: usize) -> f64 {
+    u32::try_from(count).map_or_else(|_| f64::from(u32::MAX), f64::from)
+}
+
+/// Similarity in `0.0..=1.0` as a rounded percentage.
+fn percent(similarity: f64) -> u8 {
+    let scaled = (similarity * 100.0).round();
+    (0..=100u8)
+        .rev()
+        .find(|&value| f64::from(value) <= scaled)
+        .unwrap_or(0)
+}
+
+/// Key under which two skill names are considered the same.
+fn name_key(name: &str) -> String {
+    let lowercase: String = name.nfkc().flat_map(char::to_lowercase).collect();
+    skeleton(&lowercase)
+        .filter(|c| !matches!(c, '-' | '_') && !c.is_whitespace())
+        .collect()
+}
+
+/// The declared skill name, falling back to the directory name.
+pub(crate) fn skill_name(metadata: &BTreeMap<String, Value>, skill: &SkillFile) -> String {
+    match metadata.get("name") {
+        Some(Value::String(name)) if !name.trim().is_empty() => name.trim().to_string(),
+        _ => get_dir_name(&skill.dir_path),
+    }
+}
+
+/// Check the combined description size of a catalog of skills.
+///
+/// Returns one error per exceeded limit, listing the `top` largest
+/// descriptions by that measure.
+pub fn check_catalog_budget(skills: &[SkillFile], budget: &CatalogBudget) -> Vec<ValidationError> {
+    if !budget.is_enabled() {
+        return Vec::new();
+    }
+
+    let sizes = description_sizes(skills);
+    [
+        over_budget(
+            &sizes,
+            "characters",
+            budget.max_description_chars,
+            budget.top,
+            |size| size.chars,
+        ),
+        over_budget(
+            &sizes,
+            "tokens",
+            budget.max_description_tokens,
+            budget.top,
+            |size| size.tokens,
+        ),
+    ]
+    .into_iter()
+    .flatten()
+    .collect()
+}
+
+fn over_budget(
+    sizes: &[DescriptionSize],
+    unit: &'static str,
+    limit: Option<usize>,
+    top: usize,
+    measure: fn(&DescriptionSize) -> usize,
+) -> Option<ValidationError> {
+    let limit = limit?;
+    let actual: usize = sizes.iter().map(measure).sum();
+    if actual <= limit {
+        return None;
+    }
+    let mut largest: Vec<(String, usize)> = sizes
+        .iter()
+        .map(|size| (size.skill.clone(), measure(size)))
+        .collect();
+    largest.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+    largest.truncate(top);
+    Some(ValidationError::CatalogDescriptionBudget {
+        unit,
+        limit,
+        actual,
+        skills: sizes.len(),
+        largest,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn skill(dir: &str, frontmatter: &str) -> SkillFile {
+        SkillFile {
+            dir_path: PathBuf::from(dir),
+            file_path: PathBuf::from(dir).join("SKILL.md"),
+            content: format!("---\n{frontmatter}\n---\nBody\n"),
+        }
+    }
+
+    #[test]
+    fn measures_descriptions() {
+        let skills = [
+            skill("a", "name: alpha\ndescription: Short one"),
+            skill("b", "description: Two words"),
+            skill("c", "name: gamma"),
+            skill("d", "not: [valid"),
+        ];
+        assert_eq!(
+            description_sizes(&skills),
+            [
+                DescriptionSize {
+                    skill: "alpha".to_string(),
+                    chars: 9,
+                    tokens: 2
+                },
+                DescriptionSize {
+                    skill: "b".to_string(),
+                    chars: 9,
+                    tokens: 2
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_names_that_differ_by_case_and_hyphenation() {
+        let skills = [
+            skill("plugins/a/pdf-tools", "name: pdf-tools\ndescription: A"),
+            skill("skills/pdf-tools", "name: PDF_Tools\ndescription: B"),
+            skill("skills/docx", "name: docx\ndescription: C"),
+            skill("skills/pdftools", "description: D"),
+            skill("skills/docx", "name: docx\ndescription: C"),
+        ];
+        assert_eq!(
+            find_duplicate_names(&skills, Path::new("")),
+            [ValidationError::DuplicateSkillName {
+                names: vec![
+                    "pdf-tools".to_string(),
+                    "PDF_Tools".to_string(),
+                    "pdftools".to_string()
+                ],
+                paths: vec![
+                    "plugins/a/pdf-tools".to_string(),
+                    "skills/pdf-tools".to_string(),
+                    "skills/pdftools".to_string()
+                ],
+            }]
+        );
+    }
+
+    #[test]
+    fn name_key_applies_nfkc_and_confusables() {
+        assert_eq!(name_key("ｐｄｆ－tools"), name_key("pdf-tools"));
+        assert_eq!(name_key("pdf-t\u{043e}\u{043e}ls"), name_key("pdf-tools"));
+        assert_ne!(name_key("pdf-tools"), name_key("docx-tools"));
+    }
+
+    #[test]
+    fn finds_overlapping_descriptions() {
+        let skills = [
+            skill(
+                "a",
+                "name: pdf-extract\ndescription: Extract text and tables from PDF files",
+            ),
+            skill(
+                "b",
+                "name: pdf-reader\ndescription: Use when extracting text or tables from a PDF file",
+            ),
+            skill(
+                "c",
+                "name: slack\ndescription: Post messages to Slack channels",
+            ),
+        ];
+        let overlaps = find_overlapping_descriptions(&skills, 0.5);
+        assert_eq!(overlaps.len(), 1, "{overlaps:?}");
+        assert!(matches!(
+            &overlaps[0],
+            ValidationError::OverlappingDescriptions { first, second, percent }
+                if first == "pdf-extract" && second == "pdf-reader" && *percent >= 50
+        ));
+        assert!(find_overlapping_descriptions(&skills, 0.99).is_empty());
+    }
+
+    #[test]
+    fn term_counts_drop_stop_words_and_plurals() {
+        let counts = term_counts("Use this skill when the user asks for PDFs, PDF forms or a form");
+        let terms: Vec<(&str, usize)> = counts.iter().map(|(t, c)| (t.as_str(), *c)).collect();
+        assert_eq!(terms, [("ask", 1), ("form", 2), ("pdf", 2)]);
+    }
+
+    #[test]
+    fn reports_largest_contributors_over_budget() {
+        let skills = [
+            skill("a", "name: a\ndescription: aaaa"),
+            skill("b", "name: b\ndescription: bbbbbbbbbb"),
+            skill("c", "name: c\ndescription: cccccc"),
+        ];
+        let budget = CatalogBudget {
+            max_description_chars: Some(15),
+            max_description_tokens: Some(100),
+            top: 2,
+            similarity_threshold: None,
+        };
+        assert_eq!(
+            check_catalog_budget(&skills, &budget),
+            [ValidationError::CatalogDescriptionBudget {
+                unit: "characters",
+                limit: 15,
+                actual: 20,
+                skills: 3,
+                largest: vec![("b".to_string(), 10), ("c".to_string(), 6)],
+            }]
+        );
+        assert!(check_catalog_budget(&skills, &CatalogBudget::default()).is_empty());
+    }
+}
@@ -0,0 +1,124 @@
+//! Validation of fenced code blocks in the body.
+//!
+//! Agents copy example snippets verbatim, so a JSON example with a trailing
+//! comma becomes a broken config file. [`check_code_blocks`] parses JSON, YAML
+//! and TOML blocks, reports fences without a language tag, and reports a fence
+//! that is never closed, which swallows the rest of the body.
+
+use serde::Deserialize;
+
+use crate::error::ValidationError;
+use crate::formatting::{body_with_offset, fenced_blocks};
+
+/// Check the fenced code blocks in the body of SKILL.md `content`.
+///
+/// Line numbers refer to `content`. Without frontmatter the whole content is
+/// treated as the body.
+pub fn check_code_blocks(content: &str) -> Vec<ValidationError> {
+    let (body, offset) = body_with_offset(content);
+
+    let mut errors = Vec::new();
+    for block in fenced_blocks(body) {
+        let line = offset + block.line;
+        if !block.closed {
+            errors.push(ValidationError::UnclosedCodeFence { line });
+            continue;
+        }
+        let language = block
+            .info
+            .split_whitespace()
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        if language.is_empty() {
+            errors.push(ValidationError::UnlabeledCodeFence { line });
+            continue;
+        }
+        let result = match language.as_str() {
+            "json" => parse_json(&block.content).map(|()| "JSON"),
+            "yaml" | "yml" => parse_yaml(&block.content).map(|()| "YAML"),
+            "toml" => parse_toml(&block.content).map(|()| "TOML"),
+            _ => continue,
+        };
+        if let Err((language, message)) = result {
+            errors.push(ValidationError::InvalidCodeBlock {
+                line,
+                language,
+                message,
+            });
+        }
+    }
+    errors
+}
+
+type ParseResult = Result<(), (&'static str, String)>;
+
+fn parse_json(text: &str) -> ParseResult {
+    serde_json::from_str::<serde_json::Value>(text)
+        .map(drop)
+        .map_err(|err| ("JSON", err.to_string()))
+}
+
+fn parse_yaml(text: &str) -> ParseResult {
+    // Examples may hold several `---`-separated documents.
+    for document in serde_yaml::Deserializer::from_str(text) {
+        serde_yaml::Value::deserialize(document).map_err(|err| ("YAML", err.to_string()))?;
+    }
+    Ok(())
+}
+
+fn parse_toml(text: &str) -> ParseResult {
+    text.parse::<toml::Table>()
+        .map(drop)
+        .map_err(|err| ("TOML", err.message().to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(content: &str) -> Vec<(&'static str, usize)> {
+        check_code_blocks(content)
+            .into_iter()
+            .map(|error| {
+                let line = match error {
+                    ValidationError::UnclosedCodeFence { line }
+                    | ValidationError::UnlabeledCodeFence { line }
+                    | ValidationError::InvalidCodeBlock { line, .. } => line,
+                    ref other => panic!("unexpected {other:?}"),
+                };
+                (error.code(), line)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn accepts_valid_blocks() {
+        let content = "---\nname: x\n---\n\n```json\n{\"a\": [1, 2]}\n```\n\n\
+                       ~~~yaml\na: 1\n---\nb: 2\n~~~\n\n```toml title=\"Cargo.toml\"\n[package]\nname = \"x\"\n```\n\n\
+                       ```python\ndef f(:\n```\n";
+        assert!(check_code_blocks(content).is_empty());
+    }
+
+    #[test]
+    fn reports_invalid_and_unlabeled_blocks() {
+        let content = "---\nname: x\n---\n```json\n{\"a\": 1,}\n```\n\
+                       ```YAML\na: [1\n```\n```toml\na = \n```\n```\nplain\n```\n";
+        assert_eq!(
+            codes(content),
+            [
+                ("invalid-code-block", 4),
+                ("invalid-code-block", 7),
+                ("invalid-code-block", 10),
+                ("unlabeled-code-fence", 13),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unclosed_fence() {
+        let content = "---\nname: x\n---\n\n````markdown\n```bash\nls\n```\n";
+        assert_eq!(codes(content), [("unclosed-code-fence", 5)]);
+        assert!(codes("Inline ```code``` is not a fence\n").is_empty());
+    }
+}
@@ -0,0 +1,715 @@
+//! Linter configuration.
+//!
+//! Configuration is read from `.agent-skills-lint.yaml` (or `.yml`) at the
+//! repository root, or from an explicit path. Every setting is optional; an
+//! absent file yields [`Config::default`], which lints against the open spec.
+//!
+//! # Example
+//!
+//! ```yaml
+//! profile: claude-code
+//! fields:
+//!   - name: owner
+//!     type: string
+//!     required: true
+//!     max-length: 64
+//!   - name: tier
+//!     type: enum
+//!     values: [gold, silver, bronze]
+//!   - name: ticket
+//!     type: string
+//!     pattern: "^[A-Z]+-[0-9]+$"
+//!   - name: tags
+//!     type: list
+//! rules:
+//!   orphaned-file: off
+//!   missing-reference: warn
+//! layout:
+//!   allowed-dirs: [scripts, references, assets]
+//!   max-files: 50
+//!   max-bytes: 1048576
+//! body:
+//!   max-lines: 300
+//!   max-tokens: 4000
+//! description:
+//!   max-tokens: 120
+//! catalog:
+//!   max-description-tokens: 3000
+//!   similarity-threshold: 0.6
+//! graph:
+//!   allow-cycles: false
+//! format:
+//!   list-marker: asterisk
+//!   line-endings: preserve
+//! injection:
+//!   enabled: true
+//!   disable: [base64-blob]
+//!   patterns:
+//!     - name: internal-host
+//!       pattern: "corp\\.example\\.com"
+//!       message: "refers to an internal host"
+//! metadata-keys:
+//!   - name: owner
+//!     required: true
+//!     pattern: "^@[a-z0-9-]+$"
+//!   - name: team
+//!     values: [platform, search]
+//! ```
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use regex::Regex;
+use serde::Deserialize;
+
+use crate::budget::{BodyBudget, DescriptionBudget};
+use crate::catalog::CatalogBudget;
+use crate::error::{ConfigError, Severity, ValidationError};
+use crate::graph::GraphConfig;
+use crate::injection::{builtin_patterns, is_builtin_pattern, InjectionConfig, InjectionPattern};
+use crate::layout::LayoutConfig;
+use crate::markdown::BodyFormat;
+use crate::profile::Profile;
+
+/// File names searched for at the repository root, in order of preference.
+pub const CONFIG_FILE_NAMES: [&str; 2] = [".agent-skills-lint.yaml", ".agent-skills-lint.yml"];
+
+/// Resolved linter configuration.
+#[derive(Debug, Clone, Default)]
+pub struct Config {
+    /// The runtime profile that determines allowed fields and field order.
+    pub profile: Profile,
+    /// Additional frontmatter fields, in the order they are written by `fix`.
+    pub fields: Vec<FieldSpec>,
+    /// Policy for keys inside the `metadata` mapping.
+    ///
+    /// Metadata values are always strings, so each spec is a string or enum.
+    pub metadata_keys: Vec<FieldSpec>,
+    /// Per-rule level overrides, keyed by rule code.
+    pub rules: BTreeMap<String, RuleLevel>,
+    /// Directory layout limits.
+    pub layout: LayoutConfig,
+    /// Size budget for the SKILL.md body.
+    pub body: BodyBudget,
+    /// Token budget for the `description` field.
+    pub description: DescriptionBudget,
+    /// Budget for the combined descriptions of all skills in the repository.
+    pub catalog: CatalogBudget,
+    /// Policy for references between skills.
+    pub graph: GraphConfig,
+    /// How `fix` formats the body and line endings.
+    pub format: BodyFormat,
+    /// Prompt-injection and exfiltration screening.
+    pub injection: InjectionConfig,
+}
+
+/// Configured level for a rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RuleLevel {
+    /// The rule is disabled.
+    Off,
+    /// Violations are reported as warnings.
+    Warn,
+    /// Violations are reported as errors.
+    Error,
+}
+
+/// Schema for a custom frontmatter field.
+#[derive(Debug, Clone)]
+pub struct FieldSpec {
+    /// The frontmatter key.
+    pub name: String,
+    /// The expected value type.
+    pub kind: FieldKind,
+    /// Whether the field must be present.
+    pub required: bool,
+    /// Maximum length in characters (applies to strings and list items).
+    pub max_length: Option<usize>,
+}
+
+/// Value type of a custom frontmatter field.
+#[derive(Debug, Clone)]
+pub enum FieldKind {
+    /// A string, optionally constrained by a regular expression.
+    String { pattern: Option<Regex> },
+    /// A boolean.
+    Bool,
+    /// A string drawn from a fixed set of values.
+    Enum(Vec<String>),
+    /// A list of strings.
+    List,
+}
+
+impl FieldKind {
+    /// Human-readable description of the expected value.
+    pub const fn expected(&self) -> &'static str {
+        match self {
+            Self::String { .. } | Self::Enum(_) => "a string",
+            Self::Bool => "a boolean",
+            Self::List => "a list of strings",
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawConfig {
+    profile: Option<String>,
+    #[serde(default)]
+    fields: Vec<RawFieldSpec>,
+    #[serde(default)]
+    metadata_keys: Vec<RawMetadataKeySpec>,
+    #[serde(default)]
+    rules: BTreeMap<String, RuleLevel>,
+    #[serde(default)]
+    layout: LayoutConfig,
+    #[serde(default)]
+    body: BodyBudget,
+    #[serde(default)]
+    description: DescriptionBudget,
+    #[serde(default)]
+    catalog: CatalogBudget,
+    #[serde(default)]
+    graph: GraphConfig,
+    #[serde(default)]
+    format: BodyFormat,
+    #[serde(default)]
+    injection: RawInjection,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+struct RawInjection {
+    enabled: bool,
+    disable: Vec<String>,
+    patterns: Vec<RawInjectionPattern>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawInjectionPattern {
+    name: String,
+    pattern: String,
+    message: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawFieldSpec {
+    name: String,
+    #[serde(rename = "type")]
+    kind: RawFieldKind,
+    #[serde(default)]
+    required: bool,
+    max_length: Option<usize>,
+    pattern: Option<String>,
+    values: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+struct RawMetadataKeySpec {
+    name: String,
+    #[serde(default)]
+    required: bool,
+    max_length: Option<usize>,
+    pattern: Option<String>,
+    values: Option<Vec<String>>,
+}
+
+impl From<RawMetadataKeySpec> for RawFieldSpec {
+    fn from(spec: RawMetadataKeySpec) -> Self {
+        let kind = if spec.values.is_some() {
+            RawFieldKind::Enum
+        } else {
+            RawFieldKind::String
+        };
+        Self {
+            name: spec.name,
+            kind,
+            required: spec.required,
+            max_length: spec.max_length,
+            pattern: spec.pattern,
+            values: spec.values,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+enum RawFieldKind {
+    String,
+    Bool,
+    Enum,
+    List,
+}
+
+impl Config {
+    /// Load configuration from an explicit file path.
+    pub fn load(path: &Path) -> Result<Self, ConfigError> {
+        let text = fs::read_to_string(path).map_err(|source| ConfigError::ReadFailed {
+            path: path.to_path_buf(),
+            source,
+        })?;
+        Self::parse(&text).map_err(|err| err.with_path(path))
+    }
+
+    /// Load configuration from the first config file found in `root`.
+    ///
+    /// Returns the default configuration if no config file exists.
+    pub fn discover(root: &Path) -> Result<Self, ConfigError> {
+        match find_config_file(root) {
+            Some(path) => Self::load(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    /// Parse configuration from YAML text.
+    pub fn parse(text: &str) -> Result<Self, ConfigError> {
+        if text.trim().is_empty() {
+            return Ok(Self::default());
+        }
+        let raw: RawConfig = serde_yaml::from_str(text).map_err(|err| ConfigError::Invalid {
+            path: None,
+            message: err.to_string(),
+        })?;
+
+        let profile = match raw.profile.as_deref() {
+            Some(name) => Profile::from_name(name)
+                .ok_or_else(|| ConfigError::invalid(format!("unknown profile '{name}'")))?,
+            None => Profile::default(),
+        };
+
+        let mut fields: Vec<FieldSpec> = Vec::with_capacity(raw.fields.len());
+        for spec in raw.fields {
+            if Profile::ALL
+                .iter()
+                .any(|profile| profile.allows(&spec.name))
+            {
+                return Err(ConfigError::invalid(format!(
+                    "field '{}' is built in and cannot be redefined",
+                    spec.name
+                )));
+            }
+            if fields.iter().any(|existing| existing.name == spec.name) {
+                return Err(ConfigError::invalid(format!(
+                    "field '{}' is defined more than once",
+                    spec.name
+                )));
+            }
+            fields.push(compile_field(spec)?);
+        }
+
+        let mut metadata_keys: Vec<FieldSpec> = Vec::with_capacity(raw.metadata_keys.len());
+        for spec in raw.metadata_keys {
+            if metadata_keys
+                .iter()
+                .any(|existing| existing.name == spec.name)
+            {
+                return Err(ConfigError::invalid(format!(
+                    "metadata key '{}' is defined more than once",
+                    spec.name
+                )));
+            }
+            metadata_keys.push(compile_field(spec.into())?);
+        }
+
+        if let Some(code) = raw
+            .rules
+            .keys()
+            .find(|code| !ValidationError::CODES.contains(&code.as_str()))
+        {
+            return Err(ConfigError::invalid(format!("unknown rule '{code}'")));
+        }
+
+        if let Some(threshold) = raw.catalog.similarity_threshold {
+            if !(0.0..=1.0).contains(&threshold) {
+                return Err(ConfigError::invalid(format!(
+                    "catalog.similarity-threshold must be between 0 and 1, got {threshold}"
+                )));
+            }
+        }
+
+        let injection = compile_injection(raw.injection)?;
+
+        Ok(Self {
+            profile,
+            fields,
+            metadata_keys,
+            rules: raw.rules,
+            layout: raw.layout,
+            body: raw.body,
+            description: raw.description,
+            catalog: raw.catalog,
+            graph: raw.graph,
+            format: raw.format,
+            injection,
+        })
+    }
+
+    /// Whether `field` is allowed by the profile or declared as a custom field.
+    pub fn allows(&self, field: &str) -> bool {
+        self.profile.allows(field) || self.field(field).is_some()
+    }
+
+    /// The severity to report `error` with, or `None` if its rule is off.
+    pub fn severity(&self, error: &ValidationError) -> Option<Severity> {
+        match self.rules.get(error.code()) {
+            Some(RuleLevel::Off) => None,
+            Some(RuleLevel::Warn) => Some(Severity::Warning),
+            Some(RuleLevel::Error) => Some(Severity::Error),
+            None => Some(error.default_severity()),
+        }
+    }
+
+    /// Look up the schema for a custom field.
+    pub fn field(&self, name: &str) -> Option<&FieldSpec> {
+        self.fields.iter().find(|spec| spec.name == name)
+    }
+}
+
+fn compile_injection(raw: RawInjection) -> Result<InjectionConfig, ConfigError> {
+    // Compiling the built-ins is only worth it when scanning runs.
+    let mut patterns = if raw.enabled {
+        builtin_patterns()
+    } else {
+        Vec::new()
+    };
+    for name in &raw.disable {
+        if !is_builtin_pattern(name) {
+            return Err(ConfigError::invalid(format!(
+                "injection: unknown built-in pattern '{name}'"
+            )));
+        }
+        patterns.retain(|pattern| &pattern.name != name);
+    }
+
+    let mut custom: Vec<String> = Vec::with_capacity(raw.patterns.len());
+    for spec in raw.patterns {
+        if custom.contains(&spec.name) {
+            return Err(ConfigError::invalid(format!(
+                "injection pattern '{}' is defined more than once",
+                spec.name
+            )));
+        }
+        let regex = Regex::new(&spec.pattern).map_err(|err| {
+            ConfigError::invalid(format!(
+                "injection pattern '{}': invalid pattern: {err}",
+                spec.name
+            ))
+        })?;
+        // A custom pattern with a built-in name replaces the built-in.
+        patterns.retain(|pattern| pattern.name != spec.name);
+        custom.push(spec.name.clone());
+        patterns.push(InjectionPattern {
+            name: spec.name,
+            regex,
+            message: spec.message,
+        });
+    }
+
+    Ok(InjectionConfig {
+        enabled: raw.enabled,
+        patterns,
+    })
+}
+
+fn compile_field(spec: RawFieldSpec) -> Result<FieldSpec, ConfigError> {
+    let name = spec.name;
+    let unused = |option: &str, kind: &str| {
+        ConfigError::invalid(format!(
+            "field '{name}': '{option}' is not supported for type '{kind}'"
+        ))
+    };
+
+    let kind = match spec.kind {
+        RawFieldKind::String => {
+            if spec.values.is_some() {
+                return Err(unused("values", "string"));
+            }
+            let pattern = spec
+                .pattern
+                .map(|pattern| {
+                    Regex::new(&pattern).map_err(|err| {
+                        ConfigError::invalid(format!("field '{name}': invalid pattern: {err}"))
+                    })
+                })
+                .transpose()?;
+            FieldKind::String { pattern }
+        }
+        RawFieldKind::Enum => {
+            if spec.pattern.is_some() {
+                return Err(unused("pattern", "enum"));
+            }
+            match spec.values {
+                Some(values) if !values.is_empty() => FieldKind::Enum(values),
+                _ => {
+                    return Err(ConfigError::invalid(format!(
+                        "field '{name}': type 'enum' requires a non-empty 'values' list"
+                    )))
+                }
+            }
+        }
+        RawFieldKind::Bool => {
+            if spec.pattern.is_some() {
+                return Err(unused("pattern", "bool"));
+            }
+            if spec.values.is_some() {
+                return Err(unused("values", "bool"));
+            }
+            if spec.max_length.is_some() {
+                return Err(unused("max-length", "bool"));
+            }
+            FieldKind::Bool
+        }
+        RawFieldKind::List => {
+            if spec.pattern.is_some() {
+                return Err(unused("pattern", "list"));
+            }
+            if spec.values.is_some() {
+                return Err(unused("values", "list"));
+            }
+            FieldKind::List
+        }
+    };
+
+    Ok(FieldSpec {
+        name,
+        kind,
+        required: spec.required,
+        max_length: spec.max_length,
+    })
+}
+
+/// Find the config file in `root`, if any.
+pub fn find_config_file(root: &Path) -> Option<PathBuf> {
+    CONFIG_FILE_NAMES
+        .iter()
+        .map(|name| root.join(name))
+        .find(|path| path.is_file())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::markdown::{LineEndings, ListMarker};
+
+    #[test]
+    fn parse_empty_config() {
+        let config = Config::parse("").expect("parse");
+        assert!(config.fields.is_empty());
+    }
+
+    #[test]
+    fn parse_field_specs() {
+        let config = Config::parse(
+            r#"
+fields:
+  - name: owner
+    type: string
+    required: true
+    max-length: 10
+  - name: ticket
+    type: string
+    pattern: "^[A-Z]+-[0-9]+$"
+  - name: tier
+    type: enum
+    values: [gold, silver]
+  - name: internal
+    type: bool
+  - name: tags
+    type: list
+"#,
+        )
+        .expect("parse");
+
+        let names: Vec<&str> = config.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, ["owner", "ticket", "tier", "internal", "tags"]);
+        let owner = config.field("owner").expect("owner");
+        assert!(owner.required);
+        assert_eq!(owner.max_length, Some(10));
+        assert!(matches!(
+            config.field("ticket").map(|f| &f.kind),
+            Some(FieldKind::String { pattern: Some(_) })
+        ));
+        assert!(matches!(
+            config.field("tier").map(|f| &f.kind),
+            Some(FieldKind::Enum(values)) if values == &["gold", "silver"]
+        ));
+    }
+
+    #[test]
+    fn parse_profile() {
+        let config = Config::parse("profile: claude-code\n").expect("parse");
+        assert_eq!(config.profile, Profile::ClaudeCode);
+        assert!(config.allows("model"));
+        assert!(!Config::default().allows("model"));
+    }
+
+    #[test]
+    fn parse_metadata_keys() {
+        let config = Config::parse(
+            r#"
+metadata-keys:
+  - name: owner
+    required: true
+    pattern: "^@"
+  - name: team
+    values: [platform, search]
+"#,
+        )
+        .expect("parse");
+
+        assert_eq!(config.metadata_keys.len(), 2);
+        assert!(config.metadata_keys[0].required);
+        assert!(matches!(
+            config.metadata_keys[0].kind,
+            FieldKind::String { pattern: Some(_) }
+        ));
+        assert!(matches!(config.metadata_keys[1].kind, FieldKind::Enum(_)));
+    }
+
+    #[test]
+    fn parse_rule_levels() {
+        let config = Config::parse("rules:\n  orphaned-file: error\n  missing-reference: off\n")
+            .expect("parse");
+        assert_eq!(
+            config.severity(&ValidationError::OrphanedFile("a".to_string())),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            config.severity(&ValidationError::MissingReference("a".to_string())),
+            None
+        );
+        assert_eq!(
+            config.severity(&ValidationError::NotUppercase),
+            Some(Severity::Error)
+        );
+        assert_eq!(
+            Config::default().severity(&ValidationError::OrphanedFile("a".to_string())),
+            Some(Severity::Warning)
+        );
+    }
+
+    #[test]
+    fn parse_layout() {
+        let config = Config::parse(
+            "layout:\n  allowed-dirs: [scripts]\n  disallowed-files: [\"*.log\"]\n  max-files: 5\n",
+        )
+        .expect("parse");
+        assert_eq!(
+            config.layout.allowed_dirs.as_deref(),
+            Some(&["scripts".to_string()][..])
+        );
+        assert_eq!(config.layout.disallowed_files, ["*.log"]);
+        assert_eq!(config.layout.max_files, Some(5));
+        assert_eq!(config.layout.max_bytes, None);
+        assert!(Config::default()
+            .layout
+            .disallowed_files
+            .contains(&".DS_Store".to_string()));
+    }
+
+    #[test]
+    fn parse_body_budget() {
+        let config = Config::parse("body:\n  max-lines: 300\n  max-tokens: null\n").expect("parse");
+        assert_eq!(config.body.max_lines, Some(300));
+        assert_eq!(config.body.max_tokens, None);
+        assert_eq!(config.body.toc_min_lines, 100);
+        assert_eq!(Config::default().body.max_lines, Some(500));
+
+        let config = Config::parse("description:\n  max-tokens: 80\n").expect("parse");
+        assert_eq!(config.description.max_tokens, Some(80));
+        assert_eq!(Config::default().description.max_tokens, None);
+
+        let config =
+            Config::parse("catalog:\n  max-description-chars: 8000\n  top: 3\n").expect("parse");
+        assert_eq!(config.catalog.max_description_chars, Some(8000));
+        assert_eq!(config.catalog.top, 3);
+        assert!(!Config::default().catalog.is_enabled());
+        assert_eq!(Config::default().catalog.similarity_threshold, None);
+
+        let config =
+            Config::parse("format:\n  list-marker: plus\n  line-endings: crlf\n").expect("parse");
+        assert_eq!(config.format.list_marker, ListMarker::Plus);
+        assert_eq!(config.format.line_endings, LineEndings::Crlf);
+        assert!(config.format.body);
+
+        assert!(Config::default().graph.allow_cycles);
+        let config = Config::parse("graph:\n  allow-cycles: false\n").expect("parse");
+        assert!(!config.graph.allow_cycles);
+    }
+
+    #[test]
+    fn parse_injection_patterns() {
+        assert!(!Config::default().injection.enabled);
+        assert!(Config::default().injection.patterns.is_empty());
+
+        let config = Config::parse(
+            r#"
+injection:
+  enabled: true
+  disable: [base64-blob]
+  patterns:
+    - name: internal-host
+      pattern: "corp\\.example\\.com"
+      message: refers to an internal host
+    - name: pipe-to-shell
+      pattern: "\\| *sh\\b"
+      message: pipes into a shell
+"#,
+        )
+        .expect("parse");
+        assert!(config.injection.enabled);
+        let names: Vec<&str> = config
+            .injection
+            .patterns
+            .iter()
+            .map(|pattern| pattern.name.as_str())
+            .collect();
+        assert!(!names.contains(&"base64-blob"));
+        assert!(names.ends_with(&["internal-host", "pipe-to-shell"]));
+        assert_eq!(
+            names
+                .iter()
+                .filter(|name| **name == "pipe-to-shell")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn parse_rejects_invalid_configs() {
+        for text in [
+            "profile: unknown\n",
+            "fields:\n  - name: name\n    type: string\n",
+            "fields:\n  - name: model\n    type: string\n",
+            "fields:\n  - name: a\n    type: string\n  - name: a\n    type: bool\n",
+            "fields:\n  - name: a\n    type: enum\n",
+            "fields:\n  - name: a\n    type: string\n    pattern: \"(\"\n",
+            "fields:\n  - name: a\n    type: bool\n    pattern: x\n",
+            "fields:\n  - name: a\n    type: number\n",
+            "unknown: true\n",
+            "rules:\n  not-a-rule: off\n",
+            "rules:\n  orphaned-file: loud\n",
+            "metadata-keys:\n  - name: a\n    type: bool\n",
+            "metadata-keys:\n  - name: a\n  - name: a\n",
+            "metadata-keys:\n  - name: a\n    values: []\n",
+            "layout:\n  max-files: many\n",
+            "layout:\n  unknown: true\n",
+            "body:\n  max-lines: -1\n",
+            "catalog:\n  similarity-threshold: 1.5\n",
+            "format:\n  list-marker: \"-\"\n",
+            "injection:\n  disable: [not-a-pattern]\n",
+            "injection:\n  patterns:\n    - name: a\n      pattern: \"(\"\n      message: m\n",
+            "injection:\n  patterns:\n    - name: a\n      pattern: x\n",
+        ] {
+            assert!(
+                matches!(Config::parse(text), Err(ConfigError::Invalid { .. })),
+                "{text}"
+            );
+        }
+    }
+}
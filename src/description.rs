@@ -0,0 +1,184 @@
+//! Quality heuristics for the `description` field.
+//!
+//! Agents choose a skill by reading its description, so beyond the length
+//! limits checked in [`crate::validation`] a description should say when to
+//! use the skill, be written in the third person, and be plain text.
+//! [`check_description_quality`] reports the usual ways descriptions fall short.
+
+use std::sync::LazyLock;
+
+use regex::Regex;
+
+use crate::error::ValidationError;
+use crate::formatting::DESCRIPTION_PLACEHOLDER;
+
+/// Phrases that introduce a "when to use" clause.
+const TRIGGER_PHRASES: [&str; 13] = [
+    "when",
+    "whenever",
+    "if",
+    "use for",
+    "use it for",
+    "use this for",
+    "use this skill",
+    "use this to",
+    "use to",
+    "used for",
+    "used to",
+    "trigger",
+    "triggers",
+];
+
+/// Pronouns that mark a description as first person.
+const FIRST_PERSON_WORDS: [&str; 9] = ["i", "i'm", "i'll", "i've", "i'd", "me", "my", "we", "our"];
+
+static MARKUP: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(\*\*|__|`|\[[^\]]*\]\([^)]*\)|^\s*#{1,6}\s|</?[A-Za-z][A-Za-z0-9-]*(\s[^>]*)?/?>)",
+    )
+    .expect("valid markup pattern")
+});
+
+/// Check a description for common quality problems.
+///
+/// `name` is the skill name, if known. The placeholder written by `fix` for a
+/// missing description is reported on its own, without the other checks.
+pub fn check_description_quality(description: &str, name: Option<&str>) -> Vec<ValidationError> {
+    let description = description.trim();
+    if description.is_empty() {
+        return Vec::new();
+    }
+    if description == DESCRIPTION_PLACEHOLDER {
+        return vec![ValidationError::DescriptionPlaceholder];
+    }
+
+    let lowercase = description.to_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !(c.is_alphanumeric() || c == '\'' || c == '-'))
+        .filter(|word| !word.is_empty())
+        .collect();
+
+    let mut errors = Vec::new();
+
+    if !TRIGGER_PHRASES
+        .iter()
+        .any(|phrase| contains_phrase(&words, phrase))
+    {
+        errors.push(ValidationError::DescriptionMissingTrigger);
+    }
+
+    if let Some(word) = words
+        .iter()
+        .find(|word| FIRST_PERSON_WORDS.contains(&word.replace('\u{2019}', "'").as_str()))
+    {
+        errors.push(ValidationError::DescriptionFirstPerson((*word).to_string()));
+    }
+
+    if let Some(name) = name.map(str::trim).filter(|name| !name.is_empty()) {
+        if repeats_name(&lowercase, &words, &name.to_lowercase()) {
+            errors.push(ValidationError::DescriptionRepeatsName(name.to_string()));
+        }
+    }
+
+    if let Some(markup) = MARKUP.find(description) {
+        errors.push(ValidationError::DescriptionMarkup(
+            markup.as_str().trim().to_string(),
+        ));
+    }
+
+    errors
+}
+
+/// Whether the words of `phrase` appear consecutively in `words`.
+fn contains_phrase(words: &[&str], phrase: &str) -> bool {
+    let phrase: Vec<&str> = phrase.split(' ').collect();
+    words.windows(phrase.len()).any(|window| window == phrase)
+}
+
+/// Whether the description is the name, or spells out a hyphenated name verbatim.
+fn repeats_name(description: &str, words: &[&str], name: &str) -> bool {
+    let spaced = name.replace('-', " ");
+    let without_period = description.trim_end_matches('.');
+    without_period == name
+        || without_period == spaced
+        || (name.contains('-') && words.contains(&name))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(description: &str, name: Option<&str>) -> Vec<&'static str> {
+        check_description_quality(description, name)
+            .iter()
+            .map(ValidationError::code)
+            .collect()
+    }
+
+    #[test]
+    fn good_description_passes() {
+        assert!(codes(
+            "Extracts text and tables from PDF files. Use when the user asks about PDFs.",
+            Some("pdf")
+        )
+        .is_empty());
+        assert!(codes(
+            "Formats SQL. Use for database migrations.",
+            Some("sql-format")
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn reports_placeholder_only() {
+        assert_eq!(
+            codes(DESCRIPTION_PLACEHOLDER, Some("my-skill")),
+            ["description-placeholder"]
+        );
+    }
+
+    #[test]
+    fn reports_missing_trigger_and_first_person() {
+        assert_eq!(
+            check_description_quality("I can convert spreadsheets to CSV", None),
+            [
+                ValidationError::DescriptionMissingTrigger,
+                ValidationError::DescriptionFirstPerson("i".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_repeated_name() {
+        assert_eq!(
+            codes("pdf-tools", Some("pdf-tools")),
+            ["description-missing-trigger", "description-repeats-name"]
+        );
+        assert_eq!(
+            codes(
+                "The pdf-tools skill. Use when reading PDFs.",
+                Some("pdf-tools")
+            ),
+            ["description-repeats-name"]
+        );
+        assert!(codes("Reads PDF files. Use when needed.", Some("pdf")).is_empty());
+    }
+
+    #[test]
+    fn reports_markup() {
+        for description in [
+            "Use when **bold** matters",
+            "Use when running `make`",
+            "Use when [linking](https://example.com)",
+            "Use when <b>html</b> appears",
+            "# Heading. Use when needed",
+        ] {
+            assert_eq!(
+                codes(description, None),
+                ["description-markup"],
+                "{description}"
+            );
+        }
+        assert!(codes("Use when comparing a < b and c > d", None).is_empty());
+    }
+}
@@ -193,6 +193,18 @@ pub fn discover_skills_in_dir(root: &Path) -> Vec<SkillFile> {
         .collect()
 }
 
+/// Files under `dir` that git tracks or would track, relative to `dir`.
+///
+/// Returns `None` when `dir` is not inside a git work tree. Ignored files are
+/// left out.
+pub(crate) fn git_visible_files(dir: &Path) -> Option<Vec<PathBuf>> {
+    let mut files = git_ls_files(dir, false).ok()?;
+    files.extend(git_ls_files(dir, true).ok()?);
+    files.sort();
+    files.dedup();
+    Some(files)
+}
+
 fn git_ls_files(root: &Path, untracked: bool) -> Result<Vec<PathBuf>, String> {
     let root_str = root.to_string_lossy();
     let mut args = vec!["-C", root_str.as_ref(), "ls-files", "-z"];
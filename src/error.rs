@@ -64,6 +64,34 @@ pub enum FixError {
         source: std::io::Error,
     },
 
+    /// Failed to make a script executable.
+    #[error("Failed to set permissions on {path}: {source}")]
+    PermissionsFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// No skill has the name to rename.
+    #[error("No skill named '{0}'")]
+    UnknownSkill(String),
+
+    /// Several skills have the name to rename.
+    #[error("Several skills are named '{0}'")]
+    AmbiguousSkill(String),
+
+    /// Another skill already has the new name.
+    #[error("A skill named '{0}' already exists")]
+    SkillExists(String),
+
+    /// The new name is not a valid skill name.
+    #[error("Invalid skill name '{name}': {reason}")]
+    InvalidName { name: String, reason: String },
+
+    /// The rename target already exists.
+    #[error("Target already exists: {0}")]
+    TargetExists(PathBuf),
+
     /// Failed to parse the frontmatter.
     #[error("{0}")]
     Parse(#[from] ParseError),
@@ -73,6 +101,66 @@ pub enum FixError {
     UnsupportedValueType,
 }
 
+/// Severity of a reported diagnostic.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Reported, but does not fail the check.
+    Warning,
+    /// Fails the check.
+    Error,
+}
+
+impl Severity {
+    /// Lowercase name used in output.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Warning => "warning",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Errors that can occur when loading the linter configuration.
+#[derive(Debug, Error)]
+pub enum ConfigError {
+    /// Failed to read the config file.
+    #[error("Failed to read config file {path}: {source}")]
+    ReadFailed {
+        path: PathBuf,
+        #[source]
+        source: std::io::Error,
+    },
+
+    /// The config file contents are invalid.
+    #[error("Invalid config{}: {message}", path.as_ref().map(|p| format!(" in {}", p.display())).unwrap_or_default())]
+    Invalid {
+        path: Option<PathBuf>,
+        message: String,
+    },
+}
+
+impl ConfigError {
+    pub(crate) fn invalid(message: impl Into<String>) -> Self {
+        Self::Invalid {
+            path: None,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn with_path(self, config_path: &std::path::Path) -> Self {
+        match self {
+            Self::Invalid {
+                path: None,
+                message,
+            } => Self::Invalid {
+                path: Some(config_path.to_path_buf()),
+                message,
+            },
+            other => other,
+        }
+    }
+}
+
 /// Validation errors for skill metadata.
 #[derive(Debug, Error, Clone, PartialEq, Eq)]
 pub enum ValidationError {
@@ -120,6 +208,30 @@ pub enum ValidationError {
     #[error("Field 'metadata.{key}' must be a string")]
     MetadataNonStringValue { key: String },
 
+    /// A metadata key required by config is missing.
+    #[error("Missing required key in 'metadata': {0}")]
+    MissingMetadataKey(String),
+
+    /// `metadata.version` is not a semantic version.
+    #[error(
+        "Field 'metadata.version' value '{version}' is not a valid semantic version: {reason}"
+    )]
+    InvalidVersion { version: String, reason: String },
+
+    /// The skill changed since a git revision without a version increase.
+    #[error(
+        "Skill changed since {rev} without increasing metadata.version ({previous} -> {current})"
+    )]
+    VersionNotBumped {
+        rev: String,
+        previous: String,
+        current: String,
+    },
+
+    /// Comparing against a git revision failed.
+    #[error("Failed to compare against git revision '{rev}': {reason}")]
+    GitRevision { rev: String, reason: String },
+
     /// Skill name exceeds length limit.
     #[error("Skill name '{name}' exceeds {limit} character limit ({actual} chars)")]
     NameTooLong {
@@ -152,6 +264,26 @@ pub enum ValidationError {
     #[error("Description exceeds {limit} character limit ({actual} chars)")]
     DescriptionTooLong { limit: usize, actual: usize },
 
+    /// Description is the placeholder written by `fix`.
+    #[error("Field 'description' is still the placeholder; describe what the skill does and when to use it")]
+    DescriptionPlaceholder,
+
+    /// Description does not say when to use the skill.
+    #[error("Field 'description' should say when to use the skill (e.g. \"Use when ...\")")]
+    DescriptionMissingTrigger,
+
+    /// Description is written in the first person.
+    #[error("Field 'description' should be written in the third person (found '{0}')")]
+    DescriptionFirstPerson(String),
+
+    /// Description repeats the skill name.
+    #[error("Field 'description' repeats the skill name '{0}'; describe what it does instead")]
+    DescriptionRepeatsName(String),
+
+    /// Description contains Markdown or HTML markup.
+    #[error("Field 'description' should be plain text (found markup '{0}')")]
+    DescriptionMarkup(String),
+
     /// Compatibility exceeds length limit.
     #[error("Compatibility exceeds {limit} character limit ({actual} chars)")]
     CompatibilityTooLong { limit: usize, actual: usize },
@@ -160,6 +292,18 @@ pub enum ValidationError {
     #[error("Field 'license' must be a non-empty string if provided")]
     EmptyLicense,
 
+    /// License is not a valid SPDX expression.
+    #[error("License '{license}' is not a valid SPDX license expression: {reason}")]
+    InvalidLicense { license: String, reason: String },
+
+    /// License refers to a file that does not exist in the skill directory.
+    #[error("License file '{0}' does not exist in the skill directory")]
+    LicenseFileNotFound(String),
+
+    /// License refers to a file outside the skill directory.
+    #[error("License file '{0}' must be inside the skill directory")]
+    LicenseFileOutsideSkill(String),
+
     /// Invalid tool specification format.
     #[error("Invalid tool specification '{spec}': {reason}")]
     InvalidToolSpec { spec: String, reason: String },
@@ -172,10 +316,380 @@ pub enum ValidationError {
     #[error("Field 'allowed-tools' must be a string or array of strings")]
     InvalidToolsType,
 
+    /// Body references a file that does not exist.
+    #[error("Referenced file '{0}' does not exist in the skill directory")]
+    MissingReference(String),
+
+    /// Body references a file by absolute path.
+    #[error("Reference '{0}' uses an absolute path; use a path relative to the skill directory")]
+    AbsoluteReference(String),
+
+    /// Body references a file outside the skill directory.
+    #[error("Reference '{0}' points outside the skill directory")]
+    ReferenceEscapesSkillDir(String),
+
+    /// Bundled file is never referenced from SKILL.md.
+    #[error("Bundled file '{0}' is not referenced from SKILL.md")]
+    OrphanedFile(String),
+
+    /// Top-level directory is not in the configured allow-list.
+    #[error("Directory '{0}' is not an allowed top-level directory")]
+    DisallowedDirectory(String),
+
+    /// Editor, OS or cache file that should not be shipped with a skill.
+    #[error("'{0}' is a junk file and should not be part of the skill")]
+    JunkFile(String),
+
+    /// Skill directory contains more files than allowed.
+    #[error("Skill directory contains {actual} files, exceeding the limit of {limit}")]
+    TooManyFiles { limit: usize, actual: usize },
+
+    /// Skill directory is larger than allowed.
+    #[error("Skill directory is {actual} bytes, exceeding the limit of {limit} bytes")]
+    SkillTooLarge { limit: u64, actual: u64 },
+
+    /// Binary file outside the `assets/` directory.
+    #[error("Binary file '{0}' should be placed under assets/")]
+    BinaryOutsideAssets(String),
+
+    /// SKILL.md body exceeds its size budget.
+    #[error("SKILL.md body has {actual} {unit}, over the budget of {limit}; move detailed content into references/")]
+    BodyTooLong {
+        unit: &'static str,
+        limit: usize,
+        actual: usize,
+    },
+
+    /// SKILL.md body exceeds its token budget.
+    #[error("SKILL.md body is about {actual} tokens, over the budget of {limit}; move detailed content into references/")]
+    BodyTokenBudget { limit: usize, actual: usize },
+
+    /// Description exceeds its token budget.
+    #[error("Field 'description' is about {actual} tokens, over the budget of {limit}")]
+    DescriptionTokenBudget { limit: usize, actual: usize },
+
+    /// Combined descriptions of all skills exceed the catalog budget.
+    #[error(
+        "Descriptions of {skills} skills total {actual} {unit}, over the catalog budget of {limit}; largest: {}",
+        format_contributors(.largest)
+    )]
+    CatalogDescriptionBudget {
+        unit: &'static str,
+        limit: usize,
+        actual: usize,
+        skills: usize,
+        largest: Vec<(String, usize)>,
+    },
+
+    /// Several skills in the catalog share a name.
+    #[error(
+        "Conflicting skill names {names:?} (ignoring case and hyphenation) in: {}",
+        .paths.join(", ")
+    )]
+    DuplicateSkillName {
+        names: Vec<String>,
+        paths: Vec<String>,
+    },
+
+    /// Two skills have descriptions similar enough to compete for the same requests.
+    #[error("Descriptions of '{first}' and '{second}' are {percent}% similar; agents may pick the wrong skill")]
+    OverlappingDescriptions {
+        first: String,
+        second: String,
+        percent: u8,
+    },
+
+    /// SKILL.md contains an invisible or direction-changing code point.
+    #[error("Line {line} contains a hidden {kind} ({code_point})")]
+    HiddenCharacter {
+        line: usize,
+        code_point: String,
+        kind: &'static str,
+    },
+
+    /// Skill name mixes characters from different scripts.
+    #[error("Skill name '{0}' mixes characters from different scripts")]
+    MixedScriptName(String),
+
+    /// Skill name is made of characters that imitate an ASCII name.
+    #[error("Skill name '{name}' uses look-alike characters for '{lookalike}'")]
+    ConfusableName { name: String, lookalike: String },
+
+    /// A line matches a prompt-injection or exfiltration pattern.
+    #[error("{path}:{line}: {message} ({pattern})")]
+    SuspiciousInstruction {
+        path: String,
+        line: usize,
+        pattern: String,
+        message: String,
+    },
+
+    /// A file in the skill directory appears to contain a credential.
+    #[error("{path}:{line}: possible {kind} committed to the skill")]
+    CommittedSecret {
+        path: String,
+        line: usize,
+        kind: &'static str,
+    },
+
+    /// Referenced script has no `#!` line.
+    #[error("Script '{0}' has no shebang line")]
+    ScriptMissingShebang(String),
+
+    /// Referenced script lacks the executable permission bit.
+    #[error("Script '{0}' is not executable; run `fix` or `chmod +x` it")]
+    ScriptNotExecutable(String),
+
+    /// Referenced script uses CRLF or inconsistent line endings.
+    #[error("Script '{path}' uses {endings} line endings")]
+    ScriptLineEndings { path: String, endings: &'static str },
+
+    /// The shebang interpreter does not match the script's extension.
+    #[error("Script '{path}' runs with '{interpreter}' but has a .{extension} extension")]
+    ShebangMismatch {
+        path: String,
+        interpreter: String,
+        extension: String,
+    },
+
+    /// A fenced code block is never closed.
+    #[error("Code fence opened on line {line} is never closed")]
+    UnclosedCodeFence { line: usize },
+
+    /// A fenced code block has no language tag.
+    #[error("Code block on line {line} has no language tag")]
+    UnlabeledCodeFence { line: usize },
+
+    /// A JSON, YAML or TOML code block does not parse.
+    #[error("{language} code block on line {line} does not parse: {message}")]
+    InvalidCodeBlock {
+        line: usize,
+        language: &'static str,
+        message: String,
+    },
+
+    /// The body has more than one H1 heading.
+    #[error("Line {line} has a second H1 heading; use one H1 as the title")]
+    MultipleH1 { line: usize },
+
+    /// A heading is more than one level deeper than the previous heading.
+    #[error("Heading on line {line} skips from H{from} to H{to}")]
+    SkippedHeadingLevel { line: usize, from: usize, to: usize },
+
+    /// The H1 heading does not mention the skill name.
+    #[error("H1 heading '{heading}' does not relate to the skill name '{name}'")]
+    HeadingUnrelatedToName { heading: String, name: String },
+
+    /// A heading has no content before the next heading of the same or higher level.
+    #[error("Section '{heading}' on line {line} is empty")]
+    EmptySection { line: usize, heading: String },
+
+    /// A line ends with spaces or tabs.
+    #[error("Line {line} has trailing whitespace")]
+    TrailingWhitespace { line: usize },
+
+    /// A line outside code blocks contains a tab character.
+    #[error("Line {line} contains a hard tab")]
+    HardTab { line: usize },
+
+    /// A `---` line in the body starts what looks like a second frontmatter block.
+    #[error("Line {line} starts what looks like a second frontmatter block")]
+    StrayFrontmatter { line: usize },
+
+    /// A skill links to, or lists as related, a skill that does not exist.
+    #[error("Skill '{from}' references unknown skill '{to}'")]
+    UnknownSkillReference { from: String, to: String },
+
+    /// Skills reference each other in a cycle.
+    #[error("Skills reference each other in a cycle: {}", .0.join(", "))]
+    SkillReferenceCycle(Vec<String>),
+
+    /// Long referenced Markdown file has no table of contents.
+    #[error("Referenced file '{path}' has {lines} lines but no table of contents")]
+    ReferenceMissingToc { path: String, lines: usize },
+
     /// Unexpected fields in frontmatter.
     #[error("Unexpected fields in frontmatter: {fields}. Only {allowed:?} are allowed.")]
     UnexpectedFields {
         fields: String,
-        allowed: &'static [&'static str],
+        allowed: Vec<String>,
+    },
+
+    /// Custom field has the wrong value type.
+    #[error("Field '{field}' must be {expected}")]
+    InvalidFieldType {
+        field: String,
+        expected: &'static str,
     },
+
+    /// Custom field value exceeds its configured length limit.
+    #[error("Field '{field}' exceeds {limit} character limit ({actual} chars)")]
+    FieldTooLong {
+        field: String,
+        limit: usize,
+        actual: usize,
+    },
+
+    /// Custom enum field has a value outside the configured set.
+    #[error("Field '{field}' must be one of {allowed:?}, got '{value}'")]
+    FieldValueNotAllowed {
+        field: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+
+    /// Custom string field does not match its configured pattern.
+    #[error("Field '{field}' value '{value}' does not match pattern '{pattern}'")]
+    FieldPatternMismatch {
+        field: String,
+        value: String,
+        pattern: String,
+    },
+}
+
+/// Generates [`ValidationError::CODES`] and [`ValidationError::code`] from a
+/// single variant-to-code table, so the list cannot drift from the match.
+macro_rules! rule_codes {
+    ($($pattern:pat => $code:literal,)*) => {
+        /// Every rule code, as returned by [`ValidationError::code`].
+        pub const CODES: [&'static str; <[&str]>::len(&[$($code),*])] = [$($code),*];
+
+        /// Stable, machine-readable code identifying the rule that produced this error.
+        pub const fn code(&self) -> &'static str {
+            match self {
+                $($pattern => $code,)*
+            }
+        }
+    };
+}
+
+impl ValidationError {
+    rule_codes! {
+        Self::MissingFile(_) => "missing-file",
+        Self::PathNotFound(_) => "path-not-found",
+        Self::NotADirectory(_) => "not-a-directory",
+        Self::NotUppercase => "not-uppercase",
+        Self::Parse(_) => "parse-error",
+        Self::MissingField(_) => "missing-field",
+        Self::EmptyField(_) => "empty-field",
+        Self::InvalidType(_) => "invalid-type",
+        Self::MetadataNotMapping => "metadata-not-mapping",
+        Self::MetadataNonStringKey => "metadata-non-string-key",
+        Self::MetadataNonStringValue { .. } => "metadata-non-string-value",
+        Self::MissingMetadataKey(_) => "missing-metadata-key",
+        Self::InvalidVersion { .. } => "invalid-version",
+        Self::VersionNotBumped { .. } => "version-not-bumped",
+        Self::GitRevision { .. } => "git-revision",
+        Self::NameTooLong { .. } => "name-too-long",
+        Self::NameNotLowercase(_) => "name-not-lowercase",
+        Self::NameInvalidHyphen => "name-invalid-hyphen",
+        Self::NameConsecutiveHyphens => "name-consecutive-hyphens",
+        Self::NameInvalidChars(_) => "name-invalid-chars",
+        Self::NameMismatch { .. } => "name-mismatch",
+        Self::DescriptionTooLong { .. } => "description-too-long",
+        Self::DescriptionPlaceholder => "description-placeholder",
+        Self::DescriptionMissingTrigger => "description-missing-trigger",
+        Self::DescriptionFirstPerson(_) => "description-first-person",
+        Self::DescriptionRepeatsName(_) => "description-repeats-name",
+        Self::DescriptionMarkup(_) => "description-markup",
+        Self::CompatibilityTooLong { .. } => "compatibility-too-long",
+        Self::EmptyLicense => "empty-license",
+        Self::InvalidLicense { .. } => "invalid-license",
+        Self::LicenseFileNotFound(_) => "license-file-not-found",
+        Self::LicenseFileOutsideSkill(_) => "license-file-outside-skill",
+        Self::InvalidToolSpec { .. } => "invalid-tool-spec",
+        Self::InvalidToolArrayItem { .. } => "invalid-tool-array-item",
+        Self::InvalidToolsType => "invalid-tools-type",
+        Self::MissingReference(_) => "missing-reference",
+        Self::AbsoluteReference(_) => "absolute-reference",
+        Self::ReferenceEscapesSkillDir(_) => "reference-escapes-skill-dir",
+        Self::OrphanedFile(_) => "orphaned-file",
+        Self::DisallowedDirectory(_) => "disallowed-directory",
+        Self::JunkFile(_) => "junk-file",
+        Self::TooManyFiles { .. } => "too-many-files",
+        Self::SkillTooLarge { .. } => "skill-too-large",
+        Self::BinaryOutsideAssets(_) => "binary-outside-assets",
+        Self::BodyTooLong { .. } => "body-too-long",
+        Self::BodyTokenBudget { .. } => "body-token-budget",
+        Self::DescriptionTokenBudget { .. } => "description-token-budget",
+        Self::CatalogDescriptionBudget { .. } => "catalog-description-budget",
+        Self::DuplicateSkillName { .. } => "duplicate-skill-name",
+        Self::OverlappingDescriptions { .. } => "overlapping-descriptions",
+        Self::HiddenCharacter { .. } => "hidden-character",
+        Self::MixedScriptName(_) => "mixed-script-name",
+        Self::ConfusableName { .. } => "confusable-name",
+        Self::SuspiciousInstruction { .. } => "suspicious-instruction",
+        Self::CommittedSecret { .. } => "committed-secret",
+        Self::ScriptMissingShebang(_) => "script-missing-shebang",
+        Self::ScriptNotExecutable(_) => "script-not-executable",
+        Self::ScriptLineEndings { .. } => "script-line-endings",
+        Self::ShebangMismatch { .. } => "shebang-mismatch",
+        Self::UnclosedCodeFence { .. } => "unclosed-code-fence",
+        Self::UnlabeledCodeFence { .. } => "unlabeled-code-fence",
+        Self::InvalidCodeBlock { .. } => "invalid-code-block",
+        Self::MultipleH1 { .. } => "multiple-h1",
+        Self::SkippedHeadingLevel { .. } => "skipped-heading-level",
+        Self::HeadingUnrelatedToName { .. } => "heading-unrelated-to-name",
+        Self::EmptySection { .. } => "empty-section",
+        Self::TrailingWhitespace { .. } => "trailing-whitespace",
+        Self::HardTab { .. } => "hard-tab",
+        Self::StrayFrontmatter { .. } => "stray-frontmatter",
+        Self::UnknownSkillReference { .. } => "unknown-skill-reference",
+        Self::SkillReferenceCycle(_) => "skill-reference-cycle",
+        Self::ReferenceMissingToc { .. } => "reference-missing-toc",
+        Self::UnexpectedFields { .. } => "unexpected-fields",
+        Self::InvalidFieldType { .. } => "invalid-field-type",
+        Self::FieldTooLong { .. } => "field-too-long",
+        Self::FieldValueNotAllowed { .. } => "field-value-not-allowed",
+        Self::FieldPatternMismatch { .. } => "field-pattern-mismatch",
+    }
+
+    /// Severity used when the rule is not configured.
+    pub const fn default_severity(&self) -> Severity {
+        match self {
+            Self::OrphanedFile(_)
+            | Self::JunkFile(_)
+            | Self::BinaryOutsideAssets(_)
+            | Self::BodyTooLong { .. }
+            | Self::BodyTokenBudget { .. }
+            | Self::DescriptionTokenBudget { .. }
+            | Self::OverlappingDescriptions { .. }
+            | Self::DescriptionPlaceholder
+            | Self::DescriptionMissingTrigger
+            | Self::DescriptionFirstPerson(_)
+            | Self::DescriptionRepeatsName(_)
+            | Self::DescriptionMarkup(_)
+            | Self::ReferenceMissingToc { .. }
+            | Self::UnlabeledCodeFence { .. }
+            | Self::MultipleH1 { .. }
+            | Self::SkippedHeadingLevel { .. }
+            | Self::HeadingUnrelatedToName { .. }
+            | Self::EmptySection { .. }
+            | Self::TrailingWhitespace { .. }
+            | Self::HardTab { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+fn format_contributors(largest: &[(String, usize)]) -> String {
+    largest
+        .iter()
+        .map(|(skill, size)| format!("{skill} ({size})"))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rule_codes_are_unique() {
+        let mut codes = ValidationError::CODES.to_vec();
+        codes.sort_unstable();
+        codes.dedup();
+        assert_eq!(codes.len(), ValidationError::CODES.len());
+        assert!(ValidationError::CODES.contains(&ValidationError::NotUppercase.code()));
+    }
 }
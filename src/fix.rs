@@ -10,11 +10,23 @@ use std::fs;
 use serde_yaml::{Mapping, Value};
 use unicode_normalization::UnicodeNormalization;
 
+use crate::budget::{check_body_budget, check_description_budget, check_reference_toc};
+use crate::code_blocks::check_code_blocks;
+use crate::config::Config;
+use crate::description::check_description_quality;
 use crate::discovery::{find_skill_md, get_dir_name};
-use crate::error::{FixError, ValidationError};
-use crate::formatting::{derive_description, format_frontmatter, parse_frontmatter};
+use crate::error::{FixError, Severity, ValidationError};
+use crate::formatting::{derive_description, format_frontmatter_with_config, parse_frontmatter};
+use crate::injection::scan_for_injection;
+use crate::layout::check_layout;
+use crate::markdown::{check_body_structure, format_body};
+use crate::references::{find_orphaned_files, validate_references};
+use crate::rename::{rename_dir, same_file};
+use crate::scripts::{check_scripts, fix_script_permissions};
+use crate::secrets::scan_for_secrets;
 use crate::skill::SkillFile;
-use crate::validation::validate_metadata;
+use crate::unicode::{check_name_confusables, find_hidden_characters};
+use crate::validation::{validate_metadata_with_config, validate_name};
 
 /// Result of a fix operation.
 #[derive(Debug)]
@@ -29,10 +41,62 @@ pub struct FixResult {
     pub target_path: Option<std::path::PathBuf>,
 }
 
+/// Options controlling how `fix_skill_with_options` rewrites a skill.
+#[derive(Debug, Clone, Default)]
+pub struct FixOptions {
+    /// Return the new content without writing to disk.
+    pub dry_run: bool,
+    /// Sort `allowed-tools` entries alphabetically when canonicalizing.
+    pub sort_allowed_tools: bool,
+    /// Which of `name` and the directory wins when they disagree.
+    pub name_source: NameSource,
+}
+
+/// Which of `name` and the skill directory `fix` treats as authoritative.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NameSource {
+    /// Rewrite `name` to match the directory.
+    #[default]
+    Directory,
+    /// Rename the directory to match a valid `name`.
+    Frontmatter,
+}
+
 /// Check a skill file for validation errors.
 ///
-/// Returns a list of validation errors. An empty list indicates the skill is valid.
+/// Returns a list of validation errors; warnings are left out. An empty list
+/// indicates the skill is valid.
 pub fn check_skill(skill: &SkillFile) -> Vec<ValidationError> {
+    check_skill_with_config(skill, &Config::default())
+}
+
+/// Check a skill file for validation errors using the given configuration.
+///
+/// Returns the diagnostics that are errors under `config`. An empty list
+/// indicates the skill is valid. Use [`check_skill_diagnostics`] to include
+/// warnings.
+pub fn check_skill_with_config(skill: &SkillFile, config: &Config) -> Vec<ValidationError> {
+    check_skill_diagnostics(skill, config)
+        .into_iter()
+        .filter(|(severity, _)| *severity == Severity::Error)
+        .map(|(_, error)| error)
+        .collect()
+}
+
+/// Check a skill file and return every diagnostic with its severity.
+///
+/// Diagnostics whose rule is turned off in `config` are omitted.
+pub fn check_skill_diagnostics(
+    skill: &SkillFile,
+    config: &Config,
+) -> Vec<(Severity, ValidationError)> {
+    collect_diagnostics(skill, config)
+        .into_iter()
+        .filter_map(|error| config.severity(&error).map(|severity| (severity, error)))
+        .collect()
+}
+
+fn collect_diagnostics(skill: &SkillFile, config: &Config) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
     if !skill.dir_path.exists() {
@@ -58,9 +122,56 @@ pub fn check_skill(skill: &SkillFile) -> Vec<ValidationError> {
         errors.push(ValidationError::NotUppercase);
     }
 
+    let layout_errors = check_layout(&skill.dir_path, &config.layout);
+    let junk: Vec<String> = layout_errors
+        .iter()
+        .filter_map(|error| match error {
+            ValidationError::JunkFile(path) => Some(path.clone()),
+            _ => None,
+        })
+        .collect();
+    errors.extend(layout_errors);
+
+    errors.extend(find_hidden_characters(&skill.content));
+    errors.extend(scan_for_secrets(&skill.dir_path));
+
     match parse_frontmatter(&skill.content) {
-        Ok((metadata, _body)) => {
-            errors.extend(validate_metadata(&metadata, Some(&skill.dir_path)));
+        Ok((metadata, body)) => {
+            errors.extend(validate_metadata_with_config(
+                &metadata,
+                Some(&skill.dir_path),
+                config,
+            ));
+            errors.extend(validate_references(&body, &skill.dir_path));
+            errors.extend(
+                find_orphaned_files(&skill.dir_path, &body)
+                    .into_iter()
+                    // Junk files are already reported; orphaning them is noise.
+                    .filter(|path| !junk.iter().any(|junk| is_within(path, junk)))
+                    .map(ValidationError::OrphanedFile),
+            );
+            let name = match metadata.get("name") {
+                Some(Value::String(name)) => Some(name.as_str()),
+                _ => None,
+            };
+            errors.extend(name.and_then(check_name_confusables));
+            if let Some(Value::String(description)) = metadata.get("description") {
+                errors.extend(check_description_quality(description, name));
+                errors.extend(check_description_budget(description, &config.description));
+            }
+            errors.extend(check_body_structure(&skill.content, name));
+            errors.extend(check_code_blocks(&skill.content));
+            errors.extend(check_body_budget(&body, &config.body));
+            errors.extend(check_reference_toc(&skill.dir_path, &body, &config.body));
+            errors.extend(check_scripts(&skill.dir_path, &body));
+            if config.injection.enabled {
+                errors.extend(scan_for_injection(
+                    &skill.dir_path,
+                    &skill.content,
+                    &body,
+                    &config.injection.patterns,
+                ));
+            }
         }
         Err(err) => errors.push(ValidationError::Parse(err)),
     }
@@ -74,12 +185,39 @@ pub fn check_skill(skill: &SkillFile) -> Vec<ValidationError> {
 /// - Rename `skill.md` to `SKILL.md` if needed
 /// - Strip UTF-8 BOM if present
 /// - Generate frontmatter if missing
-/// - Fix the name field to match the directory
+/// - Fix the name field to match the directory, or with
+///   [`NameSource::Frontmatter`] rename the directory to match the name, or
+///   report an invalid name and leave both unchanged
 /// - Generate description if missing
 /// - Normalize the metadata field
+/// - Canonicalize `allowed-tools` into a deduplicated, space-delimited string
+/// - Format the body: ATX headings, blank lines around headings and fences,
+///   one bullet marker, no trailing whitespace, and a final newline
+/// - Write the configured line endings
+/// - Set the executable bit on referenced scripts under `scripts/`
 ///
 /// If `dry_run` is true, returns the new content without writing to disk.
 pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
+    fix_skill_with_options(
+        skill,
+        &FixOptions {
+            dry_run,
+            ..FixOptions::default()
+        },
+        &Config::default(),
+    )
+}
+
+/// Fix a skill file using the given options and configuration.
+///
+/// See [`fix_skill`] for the list of repairs performed. Custom fields declared
+/// in `config` are written after the built-in fields in their configured order.
+pub fn fix_skill_with_options(
+    skill: &SkillFile,
+    options: &FixOptions,
+    config: &Config,
+) -> FixResult {
+    let dry_run = options.dry_run;
     let mut errors = Vec::new();
     let mut changed = false;
 
@@ -150,6 +288,7 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
     let mut metadata: BTreeMap<String, Value>;
     let body: String;
     let dir_name = get_dir_name(&skill.dir_path);
+    let mut new_dir = None;
 
     if content.starts_with("---") {
         match parse_frontmatter(&content) {
@@ -173,9 +312,26 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
         match metadata.get("name") {
             Some(Value::String(name)) if !name.trim().is_empty() => {
                 let name_norm: String = name.trim().nfkc().collect();
-                if name_norm != dir_name_norm {
+                if name_norm == dir_name_norm {
+                    // Already consistent.
+                } else if options.name_source == NameSource::Directory {
                     metadata.insert("name".to_string(), Value::String(dir_name));
                     changed = true;
+                } else if let Some(error) = validate_name(&name_norm, None).into_iter().next() {
+                    // Neither side is known to be right; leave both for the user.
+                    errors.push(FixError::InvalidName {
+                        name: name_norm,
+                        reason: error.to_string(),
+                    });
+                } else {
+                    let target = skill.dir_path.with_file_name(&name_norm);
+                    // A case-only rename finds the skill itself on case-insensitive filesystems.
+                    if target.exists() && !same_file(&skill.dir_path, &target) {
+                        errors.push(FixError::TargetExists(target));
+                    } else {
+                        new_dir = Some(target);
+                        changed = true;
+                    }
                 }
             }
             _ => {
@@ -202,6 +358,15 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
                 changed = true;
             }
         }
+
+        if let Some(tools) = metadata.get_mut("allowed-tools") {
+            if let Some(canonical) = canonicalize_allowed_tools(tools, options.sort_allowed_tools) {
+                if *tools != canonical {
+                    *tools = canonical;
+                    changed = true;
+                }
+            }
+        }
     } else {
         metadata = BTreeMap::new();
         metadata.insert("name".to_string(), Value::String(dir_name));
@@ -215,7 +380,12 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
 
     metadata.retain(|_, value| !matches!(value, Value::Null));
 
-    let formatted = match format_frontmatter(&metadata) {
+    match fix_script_permissions(&skill.dir_path, &body, dry_run) {
+        Ok(scripts) => changed |= !scripts.is_empty(),
+        Err(err) => errors.push(err),
+    }
+
+    let formatted = match format_frontmatter_with_config(&metadata, config) {
         Ok(result) => result,
         Err(err) => {
             errors.push(err);
@@ -228,9 +398,15 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
         }
     };
 
+    let body = if config.format.body {
+        format_body(&body, &config.format)
+    } else {
+        body
+    };
     let mut new_content = format!("{formatted}\n\n{body}");
     new_content = new_content.trim_end().to_string();
     new_content.push('\n');
+    new_content = config.format.line_endings.apply(&new_content, &content);
 
     if new_content != content {
         changed = true;
@@ -251,6 +427,20 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
         }
     }
 
+    if let Some(new_dir) = new_dir {
+        let file_name = skill_path.file_name().map(ToOwned::to_owned);
+        let renamed = if dry_run {
+            Ok(())
+        } else {
+            rename_dir(&skill.dir_path, &new_dir)
+        };
+        match (renamed, file_name) {
+            (Ok(()), Some(file_name)) => skill_path = new_dir.join(file_name),
+            (Err(err), _) => errors.push(err),
+            (Ok(()), None) => {}
+        }
+    }
+
     FixResult {
         changed,
         errors,
@@ -259,6 +449,76 @@ pub fn fix_skill(skill: &SkillFile, dry_run: bool) -> FixResult {
     }
 }
 
+/// Whether the relative path `path` is `dir` or lies inside it.
+fn is_within(path: &str, dir: &str) -> bool {
+    path.strip_prefix(dir)
+        .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+}
+
+/// Convert `allowed-tools` into its canonical space-delimited string form.
+///
+/// Accepts the legacy array form as well as comma- or space-delimited strings.
+/// Duplicate entries are dropped, keeping the first occurrence. Returns `None`
+/// when the value cannot be canonicalized (e.g. non-string array items or an
+/// empty list), leaving it for validation to report.
+fn canonicalize_allowed_tools(value: &Value, sort: bool) -> Option<Value> {
+    let mut tools: Vec<String> = Vec::new();
+    match value {
+        Value::String(text) => tools.extend(split_tool_specs(text)),
+        Value::Sequence(seq) => {
+            for item in seq {
+                let Value::String(text) = item else {
+                    return None;
+                };
+                tools.extend(split_tool_specs(text));
+            }
+        }
+        _ => return None,
+    }
+
+    let mut seen = std::collections::BTreeSet::new();
+    tools.retain(|tool| seen.insert(tool.clone()));
+    if tools.is_empty() {
+        return None;
+    }
+    if sort {
+        tools.sort();
+    }
+
+    Some(Value::String(tools.join(" ")))
+}
+
+/// Split a tool list on commas and whitespace outside of parentheses.
+fn split_tool_specs(text: &str) -> Vec<String> {
+    let mut specs = Vec::new();
+    let mut current = String::new();
+    let mut depth = 0usize;
+
+    for ch in text.chars() {
+        match ch {
+            '(' => {
+                depth += 1;
+                current.push(ch);
+            }
+            ')' => {
+                depth = depth.saturating_sub(1);
+                current.push(ch);
+            }
+            c if depth == 0 && (c == ',' || c.is_whitespace()) => {
+                if !current.is_empty() {
+                    specs.push(std::mem::take(&mut current));
+                }
+            }
+            c => current.push(c),
+        }
+    }
+    if !current.is_empty() {
+        specs.push(current);
+    }
+
+    specs
+}
+
 fn normalize_metadata_mapping(map: &Mapping) -> Mapping {
     let mut normalized = Mapping::new();
     for (key, value) in map {
@@ -281,3 +541,59 @@ fn yaml_value_to_string(value: &Value) -> String {
             .unwrap_or_else(|_| format!("{value:?}")),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strings(items: &[&str]) -> Value {
+        Value::Sequence(
+            items
+                .iter()
+                .map(|item| Value::String((*item).to_string()))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn canonicalize_allowed_tools_from_array() {
+        let value = strings(&["Bash(git:*)", "Read", "Bash(git:*)"]);
+        assert_eq!(
+            canonicalize_allowed_tools(&value, false),
+            Some(Value::String("Bash(git:*) Read".to_string()))
+        );
+    }
+
+    #[test]
+    fn canonicalize_allowed_tools_from_comma_string() {
+        let value = Value::String("Write, Bash(jq:*),Read  Write".to_string());
+        assert_eq!(
+            canonicalize_allowed_tools(&value, false),
+            Some(Value::String("Write Bash(jq:*) Read".to_string()))
+        );
+        assert_eq!(
+            canonicalize_allowed_tools(&value, true),
+            Some(Value::String("Bash(jq:*) Read Write".to_string()))
+        );
+    }
+
+    #[test]
+    fn canonicalize_allowed_tools_keeps_commas_inside_parens() {
+        let value = Value::String("Bash(a,b),Read".to_string());
+        assert_eq!(
+            canonicalize_allowed_tools(&value, false),
+            Some(Value::String("Bash(a,b) Read".to_string()))
+        );
+    }
+
+    #[test]
+    fn canonicalize_allowed_tools_rejects_invalid_values() {
+        let mut seq = strings(&["Read"]);
+        if let Value::Sequence(items) = &mut seq {
+            items.push(Value::Number(1.into()));
+        }
+        assert_eq!(canonicalize_allowed_tools(&seq, false), None);
+        assert_eq!(canonicalize_allowed_tools(&strings(&[]), false), None);
+        assert_eq!(canonicalize_allowed_tools(&Value::Bool(true), false), None);
+    }
+}
@@ -7,8 +7,11 @@ use std::collections::BTreeMap;
 
 use serde_yaml::{Mapping, Value};
 
+use crate::config::Config;
 use crate::error::{FixError, ParseError};
-use crate::skill::FIELD_ORDER;
+
+/// Description written by `fix` when a skill has none and nothing can be derived.
+pub const DESCRIPTION_PLACEHOLDER: &str = "Describe what this skill does and when to use it";
 
 /// Parse YAML frontmatter from file content.
 ///
@@ -73,6 +76,16 @@ fn split_frontmatter(content: &str) -> Result<(&str, &str), ParseError> {
     Err(ParseError::UnclosedFrontmatter)
 }
 
+/// The untrimmed body of SKILL.md `content` and the number of lines before it.
+///
+/// Without frontmatter the whole content is the body.
+pub(crate) fn body_with_offset(content: &str) -> (&str, usize) {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let body = split_frontmatter(content).map_or(content, |(_, body)| body);
+    let offset = content[..content.len() - body.len()].matches('\n').count();
+    (body, offset)
+}
+
 fn trim_line_ending(line: &str) -> &str {
     let line = line.strip_suffix('\n').unwrap_or(line);
     line.strip_suffix('\r').unwrap_or(line)
@@ -95,9 +108,21 @@ fn trim_line_ending(line: &str) -> &str {
 ///
 /// Returns `FixError::UnsupportedValueType` if a value cannot be formatted.
 pub fn format_frontmatter(metadata: &BTreeMap<String, Value>) -> Result<String, FixError> {
+    format_frontmatter_with_config(metadata, &Config::default())
+}
+
+/// Format metadata into YAML frontmatter using the field order of the
+/// configured profile, followed by the custom fields declared in `config`
+/// in their configured order.
+///
+/// Any remaining unknown fields follow in alphabetical order.
+pub fn format_frontmatter_with_config(
+    metadata: &BTreeMap<String, Value>,
+    config: &Config,
+) -> Result<String, FixError> {
     let mut lines: Vec<String> = vec!["---".to_string()];
 
-    for field in FIELD_ORDER {
+    for &field in config.profile.field_order() {
         let Some(value) = metadata.get(field) else {
             continue;
         };
@@ -125,17 +150,27 @@ pub fn format_frontmatter(metadata: &BTreeMap<String, Value>) -> Result<String,
         lines.push(format!("{}: {}", field, format_yaml_scalar(value)?));
     }
 
-    let unknown_fields: Vec<String> = metadata
-        .keys()
-        .filter(|key| !crate::skill::ALLOWED_FIELDS.contains(&key.as_str()))
-        .cloned()
-        .collect();
+    for spec in &config.fields {
+        if let Some(value) = metadata.get(&spec.name) {
+            push_extra_field(&mut lines, &spec.name, value)?;
+        }
+    }
+
+    let unknown_fields = metadata.iter().filter(|(key, _)| !config.allows(key));
 
-    for key in unknown_fields {
-        let value = metadata.get(&key).expect("key exists");
-        if let Value::Mapping(map) = value {
+    for (key, value) in unknown_fields {
+        push_extra_field(&mut lines, key, value)?;
+    }
+
+    lines.push("---".to_string());
+    Ok(lines.join("\n"))
+}
+
+fn push_extra_field(lines: &mut Vec<String>, key: &str, value: &Value) -> Result<(), FixError> {
+    match value {
+        Value::Mapping(map) => {
             let normalized = mapping_to_string_map(map)?;
-            lines.push(format!("{}:", format_key(&key)));
+            lines.push(format!("{}:", format_key(key)));
             for (sub_key, sub_val) in normalized {
                 lines.push(format!(
                     "  {}: {}",
@@ -143,17 +178,21 @@ pub fn format_frontmatter(metadata: &BTreeMap<String, Value>) -> Result<String,
                     format_string_value(&sub_val)
                 ));
             }
-        } else {
-            lines.push(format!(
-                "{}: {}",
-                format_key(&key),
-                format_yaml_scalar(value)?
-            ));
         }
+        Value::Sequence(items) if !items.is_empty() => {
+            lines.push(format!("{}:", format_key(key)));
+            for item in items {
+                lines.push(format!("  - {}", format_yaml_scalar(item)?));
+            }
+        }
+        Value::Sequence(_) => lines.push(format!("{}: []", format_key(key))),
+        _ => lines.push(format!(
+            "{}: {}",
+            format_key(key),
+            format_yaml_scalar(value)?
+        )),
     }
-
-    lines.push("---".to_string());
-    Ok(lines.join("\n"))
+    Ok(())
 }
 
 /// Convert a YAML Value to a string representation.
@@ -315,7 +354,70 @@ pub fn derive_description(body: &str) -> String {
         }
     }
 
-    "Describe what this skill does and when to use it".to_string()
+    DESCRIPTION_PLACEHOLDER.to_string()
+}
+
+/// A fenced code block in Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct FencedBlock {
+    /// Line of the opening fence, counted from 1.
+    pub line: usize,
+    /// The info string after the opening fence, trimmed.
+    pub info: String,
+    /// The lines between the fences.
+    pub content: String,
+    /// Whether a closing fence was found.
+    pub closed: bool,
+}
+
+/// Extract fenced code blocks from Markdown, using the same fence tracking as
+/// [`derive_description`].
+///
+/// A closing fence uses the opening character at least as many times and has
+/// no info string. A block left open at the end of the text is returned with
+/// `closed` unset.
+pub(crate) fn fenced_blocks(markdown: &str) -> Vec<FencedBlock> {
+    let mut blocks = Vec::new();
+    let mut open: Option<((char, usize), FencedBlock)> = None;
+
+    for (index, raw) in markdown.lines().enumerate() {
+        let line = raw.trim();
+        let fence = parse_code_fence(line).map(|(c, count)| (c, count, line[count..].trim()));
+
+        if let Some(((open_char, open_count), block)) = &mut open {
+            match fence {
+                Some((c, count, "")) if c == *open_char && count >= *open_count => {
+                    block.closed = true;
+                    blocks.push(block.clone());
+                    open = None;
+                }
+                _ => {
+                    block.content.push_str(raw);
+                    block.content.push('\n');
+                }
+            }
+            continue;
+        }
+
+        if let Some((c, count, info)) = fence {
+            // Backticks in a backtick info string make the line inline code.
+            if c == '`' && info.contains('`') {
+                continue;
+            }
+            open = Some((
+                (c, count),
+                FencedBlock {
+                    line: index + 1,
+                    info: info.to_string(),
+                    content: String::new(),
+                    closed: false,
+                },
+            ));
+        }
+    }
+
+    blocks.extend(open.map(|(_, block)| block));
+    blocks
 }
 
 /// Parse a code fence line, returning the fence character and count.
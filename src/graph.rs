@@ -0,0 +1,378 @@
+//! Cross-skill reference graph.
+//!
+//! Skills that cooperate refer to each other by name, either with a Markdown
+//! link such as `[extract text](skill:pdf-tools)` or with a comma- or
+//! space-separated `metadata.related` value. [`build_skill_graph`] collects
+//! these references across a catalog, and [`check_skill_graph`] reports
+//! references to skills that do not exist and, if configured, cycles.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use pulldown_cmark::{Event, Parser, Tag};
+use serde::Deserialize;
+use serde_yaml::Value;
+
+use crate::catalog::skill_name;
+use crate::discovery::{display_path, get_dir_name};
+use crate::error::ValidationError;
+use crate::formatting::parse_frontmatter;
+use crate::skill::SkillFile;
+
+/// URL scheme of a Markdown link to another skill.
+pub const SKILL_LINK_SCHEME: &str = "skill:";
+
+/// Metadata key listing related skills.
+pub const RELATED_KEY: &str = "related";
+
+/// Policy for the skill reference graph.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct GraphConfig {
+    /// Whether skills may reference each other in a cycle.
+    pub allow_cycles: bool,
+}
+
+impl Default for GraphConfig {
+    fn default() -> Self {
+        Self { allow_cycles: true }
+    }
+}
+
+/// Skills and the references between them.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SkillGraph {
+    /// Every skill, sorted by name.
+    pub skills: Vec<SkillNode>,
+    /// References in order of `from`, then as written.
+    pub edges: Vec<SkillEdge>,
+}
+
+/// A skill in the graph.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillNode {
+    /// The skill name, falling back to the directory name.
+    pub name: String,
+    /// The skill directory, relative to the repository root.
+    pub path: String,
+}
+
+/// A reference from one skill to another.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SkillEdge {
+    /// The referencing skill.
+    pub from: String,
+    /// The referenced skill name.
+    pub to: String,
+    /// Where the reference was written.
+    pub kind: EdgeKind,
+    /// Whether no skill in the graph has the referenced name.
+    pub missing: bool,
+}
+
+/// Where a skill reference was written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// A `skill:` link in the body.
+    Link,
+    /// An entry in `metadata.related`.
+    Metadata,
+}
+
+impl EdgeKind {
+    /// Name used in graph exports.
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Link => "link",
+            Self::Metadata => "metadata",
+        }
+    }
+}
+
+/// Build the reference graph of a catalog of skills.
+///
+/// Skills whose SKILL.md cannot be parsed are included without references.
+/// When several skills share a name, the first one is kept. Self-references
+/// are ignored.
+pub fn build_skill_graph(skills: &[SkillFile], root: &Path) -> SkillGraph {
+    let mut nodes: BTreeMap<String, (String, Vec<(String, EdgeKind)>)> = BTreeMap::new();
+    for skill in skills {
+        let (name, references) = match parse_frontmatter(&skill.content) {
+            Ok((metadata, body)) => {
+                let name = skill_name(&metadata, skill);
+                (name, skill_references(&metadata, &body))
+            }
+            Err(_) => (get_dir_name(&skill.dir_path), Vec::new()),
+        };
+        nodes
+            .entry(name)
+            .or_insert_with(|| (display_path(&skill.dir_path, root), references));
+    }
+
+    let mut graph = SkillGraph::default();
+    for (name, (_, references)) in &nodes {
+        for (to, kind) in references {
+            if to == name {
+                continue;
+            }
+            graph.edges.push(SkillEdge {
+                from: name.clone(),
+                to: to.clone(),
+                kind: *kind,
+                missing: !nodes.contains_key(to),
+            });
+        }
+    }
+    graph.skills = nodes
+        .into_iter()
+        .map(|(name, (path, _))| SkillNode { name, path })
+        .collect();
+    graph
+}
+
+/// Report references to unknown skills and, unless allowed, reference cycles.
+pub fn check_skill_graph(graph: &SkillGraph, config: &GraphConfig) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for edge in graph.edges.iter().filter(|edge| edge.missing) {
+        let error = ValidationError::UnknownSkillReference {
+            from: edge.from.clone(),
+            to: edge.to.clone(),
+        };
+        if !errors.contains(&error) {
+            errors.push(error);
+        }
+    }
+
+    if !config.allow_cycles {
+        errors.extend(
+            find_cycles(graph)
+                .into_iter()
+                .map(ValidationError::SkillReferenceCycle),
+        );
+    }
+    errors
+}
+
+/// Skill names referenced by `skill:` links and `metadata.related`, once per kind.
+fn skill_references(metadata: &BTreeMap<String, Value>, body: &str) -> Vec<(String, EdgeKind)> {
+    let mut references: Vec<(String, EdgeKind)> = Vec::new();
+    let mut push = |name: &str, kind: EdgeKind| {
+        let name = name.trim();
+        if !name.is_empty()
+            && !references
+                .iter()
+                .any(|(existing, existing_kind)| existing == name && *existing_kind == kind)
+        {
+            references.push((name.to_string(), kind));
+        }
+    };
+
+    for event in Parser::new(body) {
+        if let Event::Start(Tag::Link { dest_url, .. }) = event {
+            if let Some(name) = link_target(&dest_url) {
+                push(name, EdgeKind::Link);
+            }
+        }
+    }
+
+    if let Some(Value::Mapping(map)) = metadata.get("metadata") {
+        if let Some(Value::String(related)) = map.get(RELATED_KEY) {
+            for name in related.split(|c: char| c == ',' || c.is_whitespace()) {
+                push(name, EdgeKind::Metadata);
+            }
+        }
+    }
+
+    references
+}
+
+/// The skill named by a `skill:` link destination, without any fragment.
+pub(crate) fn link_target(dest: &str) -> Option<&str> {
+    let target = dest.strip_prefix(SKILL_LINK_SCHEME)?;
+    target.split(['#', '?']).next()
+}
+
+/// Groups of skills that reach each other, as sorted names.
+///
+/// Uses Tarjan's strongly connected components algorithm; only components
+/// with more than one skill are cycles, since self-references are dropped.
+fn find_cycles(graph: &SkillGraph) -> Vec<Vec<String>> {
+    let names: Vec<&str> = graph.skills.iter().map(|node| node.name.as_str()).collect();
+    let adjacency: Vec<Vec<usize>> = names
+        .iter()
+        .map(|name| {
+            graph
+                .edges
+                .iter()
+                .filter(|edge| edge.from == *name)
+                .filter_map(|edge| names.iter().position(|other| *other == edge.to))
+                .collect()
+        })
+        .collect();
+
+    let mut tarjan = Tarjan {
+        adjacency: &adjacency,
+        index: vec![None; names.len()],
+        low: vec![0; names.len()],
+        on_stack: vec![false; names.len()],
+        stack: Vec::new(),
+        next: 0,
+        components: Vec::new(),
+    };
+    for node in 0..names.len() {
+        if tarjan.index[node].is_none() {
+            tarjan.visit(node);
+        }
+    }
+
+    let mut cycles: Vec<Vec<String>> = tarjan
+        .components
+        .into_iter()
+        .filter(|component| component.len() > 1)
+        .map(|component| {
+            let mut cycle: Vec<String> = component
+                .into_iter()
+                .map(|node| names[node].to_string())
+                .collect();
+            cycle.sort();
+            cycle
+        })
+        .collect();
+    cycles.sort();
+    cycles
+}
+
+struct Tarjan<'a> {
+    adjacency: &'a [Vec<usize>],
+    index: Vec<Option<usize>>,
+    low: Vec<usize>,
+    on_stack: Vec<bool>,
+    stack: Vec<usize>,
+    next: usize,
+    components: Vec<Vec<usize>>,
+}
+
+impl Tarjan<'_> {
+    fn visit(&mut self, node: usize) {
+        self.index[node] = Some(self.next);
+        self.low[node] = self.next;
+        self.next += 1;
+        self.stack.push(node);
+        self.on_stack[node] = true;
+
+        for &next in &self.adjacency[node] {
+            match self.index[next] {
+                None => {
+                    self.visit(next);
+                    self.low[node] = self.low[node].min(self.low[next]);
+                }
+                Some(index) if self.on_stack[next] => {
+                    self.low[node] = self.low[node].min(index);
+                }
+                Some(_) => {}
+            }
+        }
+
+        if Some(self.low[node]) == self.index[node] {
+            let mut component = Vec::new();
+            while let Some(member) = self.stack.pop() {
+                self.on_stack[member] = false;
+                component.push(member);
+                if member == node {
+                    break;
+                }
+            }
+            self.components.push(component);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn skill(dir: &str, frontmatter: &str, body: &str) -> SkillFile {
+        SkillFile {
+            dir_path: PathBuf::from("skills").join(dir),
+            file_path: PathBuf::from("skills").join(dir).join("SKILL.md"),
+            content: format!("---\n{frontmatter}\n---\n{body}\n"),
+        }
+    }
+
+    fn graph() -> SkillGraph {
+        build_skill_graph(
+            &[
+                skill(
+                    "pdf",
+                    "name: pdf\nmetadata:\n  related: \"docx, ocr\"",
+                    "See [docx](skill:docx#usage) and [me](skill:pdf).",
+                ),
+                skill("docx", "name: docx", "Convert with [pdf](skill:pdf)."),
+                skill("xlsx", "name: xlsx", "Uses [csv](skill:csv)."),
+            ],
+            Path::new("."),
+        )
+    }
+
+    #[test]
+    fn builds_graph_from_links_and_metadata() {
+        let graph = graph();
+        let names: Vec<&str> = graph.skills.iter().map(|node| node.name.as_str()).collect();
+        assert_eq!(names, ["docx", "pdf", "xlsx"]);
+        assert_eq!(graph.skills[1].path, "skills/pdf");
+        let edges: Vec<(&str, &str, EdgeKind, bool)> = graph
+            .edges
+            .iter()
+            .map(|edge| {
+                (
+                    edge.from.as_str(),
+                    edge.to.as_str(),
+                    edge.kind,
+                    edge.missing,
+                )
+            })
+            .collect();
+        assert_eq!(
+            edges,
+            [
+                ("docx", "pdf", EdgeKind::Link, false),
+                ("pdf", "docx", EdgeKind::Link, false),
+                ("pdf", "docx", EdgeKind::Metadata, false),
+                ("pdf", "ocr", EdgeKind::Metadata, true),
+                ("xlsx", "csv", EdgeKind::Link, true),
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_unknown_skills_and_cycles() {
+        let graph = graph();
+        let unknown = [
+            ValidationError::UnknownSkillReference {
+                from: "pdf".to_string(),
+                to: "ocr".to_string(),
+            },
+            ValidationError::UnknownSkillReference {
+                from: "xlsx".to_string(),
+                to: "csv".to_string(),
+            },
+        ];
+        assert_eq!(check_skill_graph(&graph, &GraphConfig::default()), unknown);
+
+        let mut expected = unknown.to_vec();
+        expected.push(ValidationError::SkillReferenceCycle(vec![
+            "docx".to_string(),
+            "pdf".to_string(),
+        ]));
+        assert_eq!(
+            check_skill_graph(
+                &graph,
+                &GraphConfig {
+                    allow_cycles: false
+                }
+            ),
+            expected
+        );
+    }
+}
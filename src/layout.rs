@@ -0,0 +1,264 @@
+//! Rules for the directory structure around SKILL.md.
+//!
+//! A skill is packaged and shipped as a whole directory, so stray editor
+//! files, caches and oversized payloads matter as much as the frontmatter.
+//! [`check_layout`] walks the skill directory and applies the limits from
+//! [`LayoutConfig`].
+
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+
+use serde::Deserialize;
+use walkdir::WalkDir;
+
+use crate::error::ValidationError;
+use crate::references::relative_display;
+
+/// Number of leading bytes inspected when deciding whether a file is binary.
+const BINARY_SNIFF_LEN: usize = 8192;
+
+/// Directory layout limits for a skill.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct LayoutConfig {
+    /// Allowed top-level subdirectories. `None` allows any.
+    pub allowed_dirs: Option<Vec<String>>,
+    /// Glob patterns (`*`, `?`) for file and directory names that must not be present.
+    pub disallowed_files: Vec<String>,
+    /// Maximum number of files in the skill directory.
+    pub max_files: Option<usize>,
+    /// Maximum total size of the skill directory in bytes.
+    pub max_bytes: Option<u64>,
+}
+
+impl Default for LayoutConfig {
+    fn default() -> Self {
+        Self {
+            allowed_dirs: None,
+            disallowed_files: default_disallowed_files(),
+            max_files: None,
+            max_bytes: None,
+        }
+    }
+}
+
+fn default_disallowed_files() -> Vec<String> {
+    [
+        ".DS_Store",
+        "Thumbs.db",
+        "desktop.ini",
+        "__pycache__",
+        "*.pyc",
+        "*.swp",
+        "*.swo",
+        "*~",
+        ".#*",
+    ]
+    .iter()
+    .map(ToString::to_string)
+    .collect()
+}
+
+/// Check the files and directories inside a skill directory.
+///
+/// Reported paths are relative to the skill directory and use `/` separators.
+/// A `.git` directory is ignored, and disallowed directories are reported once
+/// without descending into them. Entries that cannot be read are skipped.
+pub fn check_layout(skill_dir: &Path, layout: &LayoutConfig) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let mut file_count = 0usize;
+    let mut total_bytes = 0u64;
+
+    let mut walker = WalkDir::new(skill_dir)
+        .min_depth(1)
+        .sort_by_file_name()
+        .into_iter();
+
+    while let Some(entry) = walker.next() {
+        let Ok(entry) = entry else {
+            continue;
+        };
+        let Ok(rel) = entry.path().strip_prefix(skill_dir) else {
+            continue;
+        };
+        let rel_display = relative_display(rel);
+        let name = entry.file_name().to_string_lossy();
+        let is_dir = entry.file_type().is_dir();
+
+        if is_dir && name == ".git" {
+            walker.skip_current_dir();
+            continue;
+        }
+
+        if layout
+            .disallowed_files
+            .iter()
+            .any(|pattern| glob_match(pattern, &name))
+        {
+            errors.push(ValidationError::JunkFile(rel_display));
+            if is_dir {
+                walker.skip_current_dir();
+            }
+            continue;
+        }
+
+        if is_dir {
+            if entry.depth() == 1 {
+                if let Some(allowed) = &layout.allowed_dirs {
+                    if !allowed.iter().any(|dir| *dir == name) {
+                        errors.push(ValidationError::DisallowedDirectory(rel_display));
+                    }
+                }
+            }
+            continue;
+        }
+
+        if !entry.file_type().is_file() {
+            continue;
+        }
+
+        file_count += 1;
+        total_bytes += entry.metadata().map(|meta| meta.len()).unwrap_or(0);
+
+        let in_assets = rel
+            .components()
+            .next()
+            .is_some_and(|first| first.as_os_str() == "assets");
+        if !in_assets && is_binary(entry.path()) {
+            errors.push(ValidationError::BinaryOutsideAssets(rel_display));
+        }
+    }
+
+    if let Some(limit) = layout.max_files {
+        if file_count > limit {
+            errors.push(ValidationError::TooManyFiles {
+                limit,
+                actual: file_count,
+            });
+        }
+    }
+
+    if let Some(limit) = layout.max_bytes {
+        if total_bytes > limit {
+            errors.push(ValidationError::SkillTooLarge {
+                limit,
+                actual: total_bytes,
+            });
+        }
+    }
+
+    errors
+}
+
+/// Whether a file looks binary (contains a NUL byte near the start).
+fn is_binary(path: &Path) -> bool {
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut buffer = Vec::with_capacity(BINARY_SNIFF_LEN);
+    if file
+        .take(BINARY_SNIFF_LEN as u64)
+        .read_to_end(&mut buffer)
+        .is_err()
+    {
+        return false;
+    }
+    buffer.contains(&0)
+}
+
+/// Match a name against a glob pattern supporting `*` and `?`.
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star_p, star_n)) = backtrack {
+            p = star_p + 1;
+            n = star_n + 1;
+            backtrack = Some((star_p, star_n + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn glob_match_wildcards() {
+        assert!(glob_match("*.swp", ".SKILL.md.swp"));
+        assert!(glob_match("*~", "notes.md~"));
+        assert!(glob_match(".#*", ".#SKILL.md"));
+        assert!(glob_match("file?.txt", "file1.txt"));
+        assert!(glob_match("__pycache__", "__pycache__"));
+        assert!(!glob_match("*.pyc", "module.py"));
+        assert!(!glob_match(".DS_Store", "DS_Store"));
+    }
+
+    #[test]
+    fn check_layout_reports_violations() {
+        let dir = TempDir::new().expect("temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("scripts").join("__pycache__")).expect("mkdir");
+        fs::create_dir_all(root.join("assets")).expect("mkdir");
+        fs::create_dir_all(root.join("build")).expect("mkdir");
+        fs::write(root.join("SKILL.md"), "---\n---\n").expect("write");
+        fs::write(root.join(".DS_Store"), "junk").expect("write");
+        fs::write(
+            root.join("scripts").join("__pycache__").join("x.pyc"),
+            "junk",
+        )
+        .expect("write");
+        fs::write(root.join("scripts").join("tool.bin"), [0u8, 1, 2]).expect("write");
+        fs::write(root.join("assets").join("logo.png"), [0u8, 1, 2]).expect("write");
+
+        let layout = LayoutConfig {
+            allowed_dirs: Some(vec!["scripts".to_string(), "assets".to_string()]),
+            max_files: Some(2),
+            max_bytes: Some(4),
+            ..LayoutConfig::default()
+        };
+        let errors = check_layout(root, &layout);
+        assert_eq!(
+            errors,
+            [
+                ValidationError::JunkFile(".DS_Store".to_string()),
+                ValidationError::DisallowedDirectory("build".to_string()),
+                ValidationError::JunkFile("scripts/__pycache__".to_string()),
+                ValidationError::BinaryOutsideAssets("scripts/tool.bin".to_string()),
+                ValidationError::TooManyFiles {
+                    limit: 2,
+                    actual: 3
+                },
+                ValidationError::SkillTooLarge {
+                    limit: 4,
+                    actual: 14
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn check_layout_default_accepts_clean_skill() {
+        let dir = TempDir::new().expect("temp dir");
+        fs::create_dir_all(dir.path().join("references")).expect("mkdir");
+        fs::write(dir.path().join("SKILL.md"), "text").expect("write");
+        fs::write(dir.path().join("references").join("a.md"), "text").expect("write");
+        assert!(check_layout(dir.path(), &LayoutConfig::default()).is_empty());
+    }
+}
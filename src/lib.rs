@@ -7,7 +7,29 @@
 //!
 //! - Validates required YAML frontmatter and field constraints
 //! - Enforces skill naming rules and directory/name matching (NFKC normalization)
-//! - Fix mode normalizes formatting and repairs common issues
+//! - Checks that files referenced from the body exist inside the skill directory
+//! - Detects hidden Unicode (bidi controls, zero-width and tag characters) and
+//!   homoglyph skill names
+//! - Warns about descriptions that lack a "when to use" clause, are written in the
+//!   first person, repeat the name, contain markup, or are still the placeholder
+//! - Warns when the body or referenced Markdown outgrows its context budget
+//! - Reports conflicting skill names across the repository, and optionally
+//!   skills whose descriptions overlap
+//! - Builds a graph of `skill:` links and `metadata.related` references between
+//!   skills, reporting unknown skills and, optionally, cycles
+//! - Enforces a budget for the combined size of every skill's description
+//! - Lints the body outline (one H1 related to the name, no skipped levels or
+//!   empty sections), whitespace, and stray frontmatter blocks
+//! - Parses JSON, YAML and TOML code blocks in the body and reports unlabeled
+//!   or unclosed fences
+//! - Checks referenced scripts for a shebang matching their extension, the
+//!   executable bit and consistent line endings
+//! - Detects committed credentials (cloud keys, tokens, private keys, high-entropy
+//!   secrets) in SKILL.md and bundled files
+//! - Optionally screens skills for prompt-injection and exfiltration instructions
+//! - Flags junk files, binaries outside `assets/` and oversized skill directories
+//! - Fix mode normalizes frontmatter and body formatting and repairs common issues
+//! - Renames a skill together with its directory and the references to it
 //! - Designed for pre-commit/prek hooks
 //!
 //! # Example
@@ -25,23 +47,67 @@
 //! }
 //! ```
 
+pub mod budget;
+pub mod catalog;
+pub mod code_blocks;
+pub mod config;
+pub mod description;
 pub mod discovery;
 pub mod error;
 pub mod fix;
 pub mod formatting;
+pub mod graph;
+pub mod injection;
+pub mod layout;
+pub mod markdown;
+pub mod profile;
+pub mod references;
+pub mod rename;
+pub mod scripts;
+pub mod secrets;
 pub mod skill;
+pub mod tokens;
+pub mod unicode;
 pub mod validation;
+pub mod version;
 
 // Re-export primary types and functions for convenience
-pub use discovery::{collect_skill_files, display_path, find_skill_md, repo_root};
-pub use error::{FixError, ParseError, ValidationError};
-pub use fix::{check_skill, fix_skill, FixResult};
-pub use formatting::{format_frontmatter, parse_frontmatter};
+pub use budget::{
+    check_body_budget, check_description_budget, check_reference_toc, BodyBudget, DescriptionBudget,
+};
+pub use catalog::{
+    check_catalog_budget, description_sizes, find_duplicate_names, find_overlapping_descriptions,
+    CatalogBudget, DescriptionSize,
+};
+pub use code_blocks::check_code_blocks;
+pub use config::Config;
+pub use description::check_description_quality;
+pub use discovery::{collect_skill_files, discover_skills, display_path, find_skill_md, repo_root};
+pub use error::{ConfigError, FixError, ParseError, Severity, ValidationError};
+pub use fix::{
+    check_skill, check_skill_diagnostics, check_skill_with_config, fix_skill,
+    fix_skill_with_options, FixOptions, FixResult, NameSource,
+};
+pub use formatting::{format_frontmatter, format_frontmatter_with_config, parse_frontmatter};
+pub use graph::{
+    build_skill_graph, check_skill_graph, EdgeKind, GraphConfig, SkillEdge, SkillGraph, SkillNode,
+};
+pub use injection::{builtin_patterns, scan_for_injection, InjectionConfig, InjectionPattern};
+pub use layout::{check_layout, LayoutConfig};
+pub use markdown::{check_body_structure, format_body, BodyFormat, LineEndings, ListMarker};
+pub use profile::Profile;
+pub use references::{extract_references, find_orphaned_files, validate_references};
+pub use rename::{apply_rename, plan_rename, FileEdit, RenamePlan};
+pub use scripts::{check_scripts, fix_script_permissions};
+pub use secrets::scan_for_secrets;
 pub use skill::{
     SkillFile, ALLOWED_FIELDS, FIELD_ORDER, MAX_COMPATIBILITY_LENGTH, MAX_DESCRIPTION_LENGTH,
     MAX_SKILL_NAME_LENGTH,
 };
-pub use validation::validate_metadata;
+pub use tokens::estimate_tokens;
+pub use unicode::{check_name_confusables, find_hidden_characters};
+pub use validation::{validate_metadata, validate_metadata_with_config};
+pub use version::check_version_bump;
 
 #[cfg(test)]
 mod tests {
@@ -341,6 +407,44 @@ mod tests {
             .any(|err| matches!(err, ValidationError::UnexpectedFields { .. })));
     }
 
+    #[test]
+    fn format_frontmatter_orders_custom_fields() {
+        let config = Config::parse(
+            "fields:\n  - name: tier\n    type: enum\n    values: [gold]\n  - name: tags\n    type: list\n",
+        )
+        .expect("config");
+
+        let mut metadata = base_metadata();
+        metadata.insert(
+            "tags".to_string(),
+            Value::Sequence(vec![
+                Value::String("a".to_string()),
+                Value::String("b".to_string()),
+            ]),
+        );
+        metadata.insert("tier".to_string(), Value::String("gold".to_string()));
+        metadata.insert("extra".to_string(), Value::Bool(true));
+
+        let formatted = format_frontmatter_with_config(&metadata, &config).expect("format");
+        let expected = [
+            "---",
+            "name: \"my-skill\"",
+            "description: \"A test skill\"",
+            "tier: \"gold\"",
+            "tags:",
+            "  - \"a\"",
+            "  - \"b\"",
+            "extra: true",
+            "---",
+        ]
+        .join("\n");
+        assert_eq!(formatted, expected);
+
+        let body = format!("{formatted}\nBody\n");
+        let (parsed, _) = parse_frontmatter(&body).expect("round trip");
+        assert_eq!(parsed, metadata);
+    }
+
     #[test]
     fn derive_description_skips_code_blocks() {
         use crate::formatting::derive_description;
@@ -473,6 +577,28 @@ Body
         assert!(errors.is_empty());
     }
 
+    #[test]
+    fn check_skill_diagnostics_includes_warnings() {
+        let dir = temp_skill_dir("true-skill");
+        let skill_dir = dir.path().join("true-skill");
+        let content = "---\nname: true-skill\ndescription: \"true\"\n---\nBody\n";
+        let skill_path = write_skill(&skill_dir, "SKILL.md", content);
+        let skill = SkillFile {
+            dir_path: skill_dir,
+            file_path: skill_path,
+            content: content.to_string(),
+        };
+
+        assert!(check_skill(&skill).is_empty());
+        assert_eq!(
+            check_skill_diagnostics(&skill, &Config::default()),
+            [(
+                Severity::Warning,
+                ValidationError::DescriptionMissingTrigger
+            )]
+        );
+    }
+
     #[test]
     fn fix_skill_preserves_unknown_fields_and_metadata() {
         let dir = temp_skill_dir("my-skill");
@@ -506,6 +632,107 @@ owner: team
         assert!(fixed.contains("owner: \"team\""));
     }
 
+    #[test]
+    fn fix_skill_canonicalizes_allowed_tools() {
+        let dir = temp_skill_dir("tools-skill");
+        let skill_dir = dir.path().join("tools-skill");
+        let content = r"---
+name: tools-skill
+description: A test skill
+allowed-tools:
+  - Read
+  - Bash(git:*)
+  - Read
+---
+Body
+";
+        let skill_path = write_skill(&skill_dir, "SKILL.md", content);
+        let skill = SkillFile {
+            dir_path: skill_dir,
+            file_path: skill_path,
+            content: content.to_string(),
+        };
+
+        let options = FixOptions {
+            dry_run: true,
+            sort_allowed_tools: true,
+            ..FixOptions::default()
+        };
+        let result = fix_skill_with_options(&skill, &options, &Config::default());
+        assert!(result.changed);
+        assert!(result.errors.is_empty());
+        let fixed = result.new_content.expect("dry-run content");
+        assert!(
+            fixed.contains("allowed-tools: \"Bash(git:*) Read\""),
+            "fixed content:\n{fixed}"
+        );
+    }
+
+    #[test]
+    fn fix_skill_renames_directory_to_match_name() {
+        let dir = temp_skill_dir("old-name");
+        let skill_dir = dir.path().join("old-name");
+        let content = "---\nname: new-name\ndescription: A test skill\n---\nBody\n";
+        let skill_path = write_skill(&skill_dir, "SKILL.md", content);
+        let skill = SkillFile {
+            dir_path: skill_dir.clone(),
+            file_path: skill_path,
+            content: content.to_string(),
+        };
+        let options = FixOptions {
+            name_source: NameSource::Frontmatter,
+            ..FixOptions::default()
+        };
+
+        fs::create_dir_all(dir.path().join("new-name")).expect("mkdir");
+        let result = fix_skill_with_options(&skill, &options, &Config::default());
+        assert!(matches!(
+            result.errors.as_slice(),
+            [FixError::TargetExists(path)] if *path == dir.path().join("new-name")
+        ));
+        assert!(skill_dir.exists());
+
+        fs::remove_dir(dir.path().join("new-name")).expect("rmdir");
+        let result = fix_skill_with_options(&skill, &options, &Config::default());
+        assert!(result.errors.is_empty(), "{:?}", result.errors);
+        assert_eq!(
+            result.target_path,
+            Some(dir.path().join("new-name").join("SKILL.md"))
+        );
+        assert!(!skill_dir.exists());
+        let fixed = fs::read_to_string(dir.path().join("new-name/SKILL.md")).expect("read");
+        assert!(
+            fixed.contains("name: \"new-name\""),
+            "fixed content:\n{fixed}"
+        );
+    }
+
+    #[test]
+    fn fix_skill_keeps_invalid_frontmatter_name() {
+        let dir = temp_skill_dir("old-name");
+        let skill_dir = dir.path().join("old-name");
+        let content = "---\nname: New_Name\ndescription: A test skill\n---\nBody\n";
+        let skill_path = write_skill(&skill_dir, "SKILL.md", content);
+        let skill = SkillFile {
+            dir_path: skill_dir.clone(),
+            file_path: skill_path,
+            content: content.to_string(),
+        };
+        let options = FixOptions {
+            name_source: NameSource::Frontmatter,
+            ..FixOptions::default()
+        };
+
+        let result = fix_skill_with_options(&skill, &options, &Config::default());
+        assert!(matches!(
+            result.errors.as_slice(),
+            [FixError::InvalidName { name, .. }] if name == "New_Name"
+        ));
+        assert!(skill_dir.exists());
+        let fixed = fs::read_to_string(skill_dir.join("SKILL.md")).expect("read");
+        assert!(fixed.contains("New_Name"), "fixed content:\n{fixed}");
+    }
+
     #[test]
     fn collect_skill_files_discovers_nested() {
         let dir = TempDir::new().expect("temp dir");
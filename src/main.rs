@@ -1,9 +1,13 @@
 use std::path::{Path, PathBuf};
 
-use clap::{Parser, Subcommand};
+use clap::{Parser, Subcommand, ValueEnum};
 
 use agent_skills_lint::{
-    check_skill, collect_skill_files, display_path, fix_skill, repo_root, FixError, ValidationError,
+    apply_rename, build_skill_graph, check_catalog_budget, check_skill_diagnostics,
+    check_skill_graph, check_version_bump, collect_skill_files, discover_skills, display_path,
+    find_duplicate_names, find_overlapping_descriptions, fix_skill_with_options, plan_rename,
+    repo_root, Config, EdgeKind, FixError, FixOptions, NameSource, Profile, Severity, SkillFile,
+    SkillGraph, ValidationError,
 };
 
 const OUTPUT_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -15,6 +19,14 @@ const OUTPUT_VERSION: &str = env!("CARGO_PKG_VERSION");
     about = "Lint and format Agent Skills"
 )]
 struct Cli {
+    /// Path to a config file (defaults to .agent-skills-lint.yaml at the repo root)
+    #[arg(long, global = true, value_name = "PATH")]
+    config: Option<PathBuf>,
+
+    /// Spec profile to lint against: strict, claude-code (overrides config)
+    #[arg(long, global = true, value_name = "NAME", value_parser = parse_profile)]
+    profile: Option<Profile>,
+
     #[command(subcommand)]
     command: Command,
 }
@@ -26,6 +38,10 @@ enum Command {
         /// Paths to check (directories or SKILL.md files)
         paths: Vec<PathBuf>,
 
+        /// Fail if a skill changed since this git revision without a metadata.version increase
+        #[arg(long, value_name = "REV")]
+        require_version_bump: Option<String>,
+
         /// Output in JSON format (machine-readable)
         #[arg(long)]
         json: bool,
@@ -43,6 +59,14 @@ enum Command {
         #[arg(long, short = 'n')]
         dry_run: bool,
 
+        /// Sort `allowed-tools` entries alphabetically
+        #[arg(long)]
+        sort_tools: bool,
+
+        /// Which wins when `name` and the directory disagree
+        #[arg(long, value_name = "SOURCE", value_enum, default_value_t = NameSourceArg::Directory)]
+        name_source: NameSourceArg,
+
         /// Output in JSON format (machine-readable)
         #[arg(long)]
         json: bool,
@@ -51,25 +75,121 @@ enum Command {
         #[arg(long, short = 'q')]
         quiet: bool,
     },
+    /// Rename a skill, its directory and the references to it
+    Rename {
+        /// Current skill name
+        old: String,
+
+        /// New skill name
+        new: String,
+
+        /// Preview changes without writing to disk
+        #[arg(long, short = 'n')]
+        dry_run: bool,
+    },
+    /// Export the graph of references between skills
+    Graph {
+        /// Paths to include (directories or SKILL.md files)
+        paths: Vec<PathBuf>,
+
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum GraphFormat {
+    /// Graphviz DOT
+    Dot,
+    /// JSON (machine-readable)
+    Json,
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum NameSourceArg {
+    /// Rewrite `name` to match the directory
+    Directory,
+    /// Rename the directory to match `name`
+    Frontmatter,
+}
+
+impl From<NameSourceArg> for NameSource {
+    fn from(source: NameSourceArg) -> Self {
+        match source {
+            NameSourceArg::Directory => Self::Directory,
+            NameSourceArg::Frontmatter => Self::Frontmatter,
+        }
+    }
 }
 
 fn main() {
     let cli = Cli::parse();
 
+    let mut config = match load_config(cli.config.as_deref()) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}");
+            std::process::exit(2);
+        }
+    };
+    if let Some(profile) = cli.profile {
+        config.profile = profile;
+    }
+
     let exit_code = match cli.command {
-        Command::Check { paths, json, quiet } => run_check(paths, json, quiet),
+        Command::Check {
+            paths,
+            require_version_bump,
+            json,
+            quiet,
+        } => run_check(paths, &config, require_version_bump.as_deref(), json, quiet),
         Command::Fix {
             paths,
             dry_run,
+            sort_tools,
+            name_source,
             json,
             quiet,
-        } => run_fix(paths, dry_run, json, quiet),
+        } => {
+            let options = FixOptions {
+                dry_run,
+                sort_allowed_tools: sort_tools,
+                name_source: name_source.into(),
+            };
+            run_fix(paths, &options, &config, json, quiet)
+        }
+        Command::Rename { old, new, dry_run } => run_rename(&old, &new, dry_run, &config),
+        Command::Graph { paths, format } => run_graph(&paths, format),
     };
 
     std::process::exit(exit_code);
 }
 
-fn run_check(paths: Vec<PathBuf>, json: bool, quiet: bool) -> i32 {
+fn load_config(path: Option<&Path>) -> Result<Config, agent_skills_lint::ConfigError> {
+    match path {
+        Some(path) => Config::load(&resolve_target_path(path, &repo_root())),
+        None => Config::discover(&repo_root()),
+    }
+}
+
+fn parse_profile(name: &str) -> Result<Profile, String> {
+    Profile::from_name(name).ok_or_else(|| {
+        let names: Vec<&str> = Profile::ALL.iter().map(|profile| profile.name()).collect();
+        format!(
+            "unknown profile '{name}' (expected one of: {})",
+            names.join(", ")
+        )
+    })
+}
+
+fn run_check(
+    paths: Vec<PathBuf>,
+    config: &Config,
+    version_rev: Option<&str>,
+    json: bool,
+    quiet: bool,
+) -> i32 {
     let root = repo_root();
     let path_issues = collect_check_path_issues(&paths, &root);
     let skill_files = collect_skill_files(&paths);
@@ -102,48 +222,306 @@ fn run_check(paths: Vec<PathBuf>, json: bool, quiet: bool) -> i32 {
         }
     }
 
-    for skill in skill_files {
-        let errors = check_skill(&skill);
+    for skill in &skill_files {
+        let mut diagnostics = check_skill_diagnostics(skill, config);
+        if let Some(rev) = version_rev {
+            diagnostics.extend(with_severity(check_version_bump(skill, rev), config));
+        }
+        let has_errors = diagnostics
+            .iter()
+            .any(|(severity, _)| *severity == Severity::Error);
         let rel = display_path(&skill.dir_path, &root);
 
         if json {
-            let error_strs: Vec<String> = errors.iter().map(format_validation_error).collect();
-            let status = if errors.is_empty() {
-                "valid"
-            } else {
-                "invalid"
-            };
+            let error_strs: Vec<String> = diagnostics
+                .iter()
+                .map(|(severity, error)| format_diagnostic(error, *severity))
+                .collect();
+            let status = if has_errors { "invalid" } else { "valid" };
             json_results.push(format!(
                 r#"{{"path":"{}","status":"{}","errors":{}}}"#,
                 escape_json(&rel),
                 status,
                 format_json_array(&error_strs)
             ));
+        } else if !quiet {
+            print_diagnostics(&rel, &diagnostics);
         }
 
-        if !errors.is_empty() {
-            if !json && !quiet {
-                eprintln!("Validation failed for {rel}:");
-                for error in &errors {
-                    eprintln!("  - {error}");
-                }
-            }
+        if has_errors {
             failed = true;
         }
     }
 
+    // Catalog checks run over every skill in the repository; findings that
+    // only involve unchecked skills are dropped.
+    let checked_paths: Vec<String> = skill_files
+        .iter()
+        .map(|skill| display_path(&skill.dir_path, &root))
+        .collect();
+    let catalog = catalog_skills(&root, skill_files);
+    let skill_graph = build_skill_graph(&catalog, &root);
+    let checked_names: Vec<&str> = skill_graph
+        .skills
+        .iter()
+        .filter(|node| checked_paths.contains(&node.path))
+        .map(|node| node.name.as_str())
+        .collect();
+
+    let mut catalog_errors: Vec<ValidationError> = find_duplicate_names(&catalog, &root)
+        .into_iter()
+        .filter(|error| match error {
+            ValidationError::DuplicateSkillName { paths, .. } => {
+                paths.iter().any(|path| checked_paths.contains(path))
+            }
+            _ => true,
+        })
+        .collect();
+    catalog_errors.extend(check_catalog_budget(&catalog, &config.catalog));
+    if let Some(threshold) = config.catalog.similarity_threshold {
+        catalog_errors.extend(
+            find_overlapping_descriptions(&catalog, threshold)
+                .into_iter()
+                .filter(|error| match error {
+                    ValidationError::OverlappingDescriptions { first, second, .. } => {
+                        checked_names.contains(&first.as_str())
+                            || checked_names.contains(&second.as_str())
+                    }
+                    _ => true,
+                }),
+        );
+    }
+    // References are resolved against every skill, but a checked skill should
+    // not fail because of another skill's links.
+    catalog_errors.extend(
+        check_skill_graph(&skill_graph, &config.graph)
+            .into_iter()
+            .filter(|error| match error {
+                ValidationError::UnknownSkillReference { from, .. } => {
+                    checked_names.contains(&from.as_str())
+                }
+                ValidationError::SkillReferenceCycle(names) => names
+                    .iter()
+                    .any(|name| checked_names.contains(&name.as_str())),
+                _ => true,
+            }),
+    );
+    let catalog_diagnostics = with_severity(catalog_errors, config);
+    if catalog_diagnostics
+        .iter()
+        .any(|(severity, _)| *severity == Severity::Error)
+    {
+        failed = true;
+    }
+
     if json {
+        let catalog_strs: Vec<String> = catalog_diagnostics
+            .iter()
+            .map(|(severity, error)| format_diagnostic(error, *severity))
+            .collect();
         println!(
-            r#"{{"version":"{}","skills":[{}]}}"#,
+            r#"{{"version":"{}","skills":[{}],"catalog":{}}}"#,
             OUTPUT_VERSION,
-            json_results.join(",")
+            json_results.join(","),
+            format_json_array(&catalog_strs)
         );
+    } else if !quiet {
+        print_diagnostics("skill catalog", &catalog_diagnostics);
     }
 
     i32::from(failed)
 }
 
-fn run_fix(paths: Vec<PathBuf>, dry_run: bool, json: bool, quiet: bool) -> i32 {
+/// Every skill in the repository, plus any checked skills outside it.
+fn catalog_skills(root: &Path, checked: Vec<SkillFile>) -> Vec<SkillFile> {
+    let mut catalog = discover_skills(root);
+    let mut known: Vec<PathBuf> = catalog
+        .iter()
+        .map(|skill| canonical(&skill.file_path))
+        .collect();
+    for skill in checked {
+        let path = canonical(&skill.file_path);
+        if !known.contains(&path) {
+            known.push(path);
+            catalog.push(skill);
+        }
+    }
+    catalog
+}
+
+fn run_rename(old: &str, new: &str, dry_run: bool, config: &Config) -> i32 {
+    let root = repo_root();
+    let plan = match plan_rename(&discover_skills(&root), old, new, config) {
+        Ok(plan) => plan,
+        Err(err) => {
+            eprintln!("Unable to rename {old}: {err}");
+            return 1;
+        }
+    };
+
+    if !dry_run {
+        if let Err(err) = apply_rename(&plan) {
+            eprintln!("Unable to rename {old}: {err}");
+            return 1;
+        }
+    }
+
+    // Report only after the rename succeeded, so a failure never follows a
+    // claim that files were changed.
+    let verb = if dry_run { "Would update" } else { "Updated" };
+    for edit in &plan.edits {
+        println!("{verb} {}", display_path(&edit.path, &root));
+        if dry_run {
+            print_line_changes(&edit.original, &edit.content);
+        }
+    }
+    if plan.old_dir != plan.new_dir {
+        let verb = if dry_run { "Would rename" } else { "Renamed" };
+        println!(
+            "{verb} {} to {}",
+            display_path(&plan.old_dir, &root),
+            display_path(&plan.new_dir, &root)
+        );
+    }
+    0
+}
+
+/// Print the lines removed from and added to a file.
+fn print_line_changes(original: &str, content: &str) {
+    let original: Vec<&str> = original.lines().collect();
+    let content: Vec<&str> = content.lines().collect();
+    for line in original.iter().filter(|line| !content.contains(line)) {
+        println!("  - {line}");
+    }
+    for line in content.iter().filter(|line| !original.contains(line)) {
+        println!("  + {line}");
+    }
+}
+
+fn run_graph(paths: &[PathBuf], format: GraphFormat) -> i32 {
+    let root = repo_root();
+    let skill_files = collect_skill_files(paths);
+    if skill_files.is_empty() {
+        eprintln!("No SKILL.md files found.");
+        return 1;
+    }
+
+    // Resolve references against the whole repository, then keep the skills
+    // under `paths` and their outgoing references.
+    let selected: Vec<String> = skill_files
+        .iter()
+        .map(|skill| display_path(&skill.dir_path, &root))
+        .collect();
+    let mut graph = build_skill_graph(&catalog_skills(&root, skill_files), &root);
+    if !paths.is_empty() {
+        graph.skills.retain(|node| selected.contains(&node.path));
+        let names: Vec<&str> = graph.skills.iter().map(|node| node.name.as_str()).collect();
+        graph
+            .edges
+            .retain(|edge| names.contains(&edge.from.as_str()));
+    }
+    match format {
+        GraphFormat::Dot => print!("{}", format_graph_dot(&graph)),
+        GraphFormat::Json => println!("{}", format_graph_json(&graph)),
+    }
+    0
+}
+
+/// Render the graph as Graphviz DOT.
+///
+/// `metadata.related` edges are dashed and unknown skills are drawn in red.
+fn format_graph_dot(graph: &SkillGraph) -> String {
+    let mut out = String::from("digraph skills {\n");
+    for node in &graph.skills {
+        out.push_str(&format!(
+            "  \"{}\" [tooltip=\"{}\"];\n",
+            escape_dot(&node.name),
+            escape_dot(&node.path)
+        ));
+    }
+    let mut missing: Vec<&str> = graph
+        .edges
+        .iter()
+        .filter(|edge| edge.missing)
+        .map(|edge| edge.to.as_str())
+        .collect();
+    missing.sort_unstable();
+    missing.dedup();
+    for name in missing {
+        out.push_str(&format!(
+            "  \"{}\" [color=red, fontcolor=red];\n",
+            escape_dot(name)
+        ));
+    }
+    for edge in &graph.edges {
+        let style = match edge.kind {
+            EdgeKind::Link => "",
+            EdgeKind::Metadata => " [style=dashed]",
+        };
+        out.push_str(&format!(
+            "  \"{}\" -> \"{}\"{style};\n",
+            escape_dot(&edge.from),
+            escape_dot(&edge.to)
+        ));
+    }
+    out.push_str("}\n");
+    out
+}
+
+fn format_graph_json(graph: &SkillGraph) -> String {
+    let skills: Vec<String> = graph
+        .skills
+        .iter()
+        .map(|node| {
+            format!(
+                r#"{{"name":"{}","path":"{}"}}"#,
+                escape_json(&node.name),
+                escape_json(&node.path)
+            )
+        })
+        .collect();
+    let edges: Vec<String> = graph
+        .edges
+        .iter()
+        .map(|edge| {
+            format!(
+                r#"{{"from":"{}","to":"{}","kind":"{}","missing":{}}}"#,
+                escape_json(&edge.from),
+                escape_json(&edge.to),
+                edge.kind.as_str(),
+                edge.missing
+            )
+        })
+        .collect();
+    format!(
+        r#"{{"version":"{}","skills":{},"edges":{}}}"#,
+        OUTPUT_VERSION,
+        format_json_array(&skills),
+        format_json_array(&edges)
+    )
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn with_severity(
+    errors: Vec<ValidationError>,
+    config: &Config,
+) -> Vec<(Severity, ValidationError)> {
+    errors
+        .into_iter()
+        .filter_map(|error| config.severity(&error).map(|severity| (severity, error)))
+        .collect()
+}
+
+fn run_fix(
+    paths: Vec<PathBuf>,
+    options: &FixOptions,
+    config: &Config,
+    json: bool,
+    quiet: bool,
+) -> i32 {
     let root = repo_root();
     let path_issues = collect_fix_path_issues(&paths, &root);
     let skill_files = collect_skill_files(&paths);
@@ -177,7 +555,7 @@ fn run_fix(paths: Vec<PathBuf>, dry_run: bool, json: bool, quiet: bool) -> i32 {
     }
 
     for skill in skill_files {
-        let result = fix_skill(&skill, dry_run);
+        let result = fix_skill_with_options(&skill, options, config);
         let rel = display_path(&skill.dir_path, &root);
 
         if json {
@@ -200,7 +578,7 @@ fn run_fix(paths: Vec<PathBuf>, dry_run: bool, json: bool, quiet: bool) -> i32 {
             ));
         } else {
             if result.changed && !quiet {
-                if dry_run {
+                if options.dry_run {
                     println!("Would fix {rel}");
                     if let Some(content) = &result.new_content {
                         // Show a preview of the frontmatter
@@ -212,6 +590,15 @@ fn run_fix(paths: Vec<PathBuf>, dry_run: bool, json: bool, quiet: bool) -> i32 {
                 } else {
                     println!("Fixed {rel}");
                 }
+                let new_dir = result.target_path.as_deref().and_then(Path::parent);
+                if let Some(new_dir) = new_dir.filter(|dir| *dir != skill.dir_path) {
+                    let verb = if options.dry_run {
+                        "Would rename"
+                    } else {
+                        "Renamed"
+                    };
+                    println!("{verb} {rel} to {}", display_path(new_dir, &root));
+                }
             }
 
             if !result.errors.is_empty() {
@@ -238,10 +625,34 @@ fn run_fix(paths: Vec<PathBuf>, dry_run: bool, json: bool, quiet: bool) -> i32 {
     i32::from(failed)
 }
 
+fn print_diagnostics(rel: &str, diagnostics: &[(Severity, ValidationError)]) {
+    for (label, wanted) in [
+        ("Validation failed for", Severity::Error),
+        ("Warnings for", Severity::Warning),
+    ] {
+        let mut matching = diagnostics
+            .iter()
+            .filter(|(severity, _)| *severity == wanted)
+            .peekable();
+        if matching.peek().is_none() {
+            continue;
+        }
+        eprintln!("{label} {rel}:");
+        for (_, error) in matching {
+            eprintln!("  - {error}");
+        }
+    }
+}
+
 fn format_validation_error(error: &ValidationError) -> String {
+    format_diagnostic(error, Severity::Error)
+}
+
+fn format_diagnostic(error: &ValidationError, severity: Severity) -> String {
     format!(
-        r#"{{"code":"{}","message":"{}"}}"#,
-        error_code(error),
+        r#"{{"code":"{}","severity":"{}","message":"{}"}}"#,
+        error.code(),
+        severity.as_str(),
         escape_json(&error.to_string())
     )
 }
@@ -254,42 +665,19 @@ fn format_fix_error(error: &FixError) -> String {
     )
 }
 
-const fn error_code(error: &ValidationError) -> &'static str {
-    match error {
-        ValidationError::MissingFile(_) => "missing-file",
-        ValidationError::PathNotFound(_) => "path-not-found",
-        ValidationError::NotADirectory(_) => "not-a-directory",
-        ValidationError::NotUppercase => "not-uppercase",
-        ValidationError::Parse(_) => "parse-error",
-        ValidationError::MissingField(_) => "missing-field",
-        ValidationError::EmptyField(_) => "empty-field",
-        ValidationError::InvalidType(_) => "invalid-type",
-        ValidationError::MetadataNotMapping => "metadata-not-mapping",
-        ValidationError::MetadataNonStringKey => "metadata-non-string-key",
-        ValidationError::MetadataNonStringValue { .. } => "metadata-non-string-value",
-        ValidationError::NameTooLong { .. } => "name-too-long",
-        ValidationError::NameNotLowercase(_) => "name-not-lowercase",
-        ValidationError::NameInvalidHyphen => "name-invalid-hyphen",
-        ValidationError::NameConsecutiveHyphens => "name-consecutive-hyphens",
-        ValidationError::NameInvalidChars(_) => "name-invalid-chars",
-        ValidationError::NameMismatch { .. } => "name-mismatch",
-        ValidationError::DescriptionTooLong { .. } => "description-too-long",
-        ValidationError::CompatibilityTooLong { .. } => "compatibility-too-long",
-        ValidationError::EmptyLicense => "empty-license",
-        ValidationError::InvalidToolSpec { .. } => "invalid-tool-spec",
-        ValidationError::InvalidToolArrayItem { .. } => "invalid-tool-array-item",
-        ValidationError::InvalidToolsType => "invalid-tools-type",
-        ValidationError::UnexpectedFields { .. } => "unexpected-fields",
-    }
-}
-
 const fn fix_error_code(error: &FixError) -> &'static str {
     match error {
         FixError::PathNotFound(_) => "path-not-found",
         FixError::NotADirectory(_) => "not-a-directory",
         FixError::RenameFailed { .. } => "rename-failed",
+        FixError::UnknownSkill(_) => "unknown-skill",
+        FixError::AmbiguousSkill(_) => "ambiguous-skill",
+        FixError::SkillExists(_) => "skill-exists",
+        FixError::InvalidName { .. } => "invalid-name",
+        FixError::TargetExists(_) => "target-exists",
         FixError::MissingFile => "missing-file",
         FixError::WriteFailed { .. } => "write-failed",
+        FixError::PermissionsFailed { .. } => "permissions-failed",
         FixError::Parse(_) => "parse-error",
         FixError::UnsupportedValueType => "unsupported-value-type",
     }
@@ -313,6 +701,20 @@ fn escape_json(s: &str) -> String {
     out
 }
 
+/// Escape a string for use inside a quoted Graphviz ID.
+fn escape_dot(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    for ch in s.chars() {
+        match ch {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            c if c.is_control() => out.push(' '),
+            _ => out.push(ch),
+        }
+    }
+    out
+}
+
 fn format_json_array(items: &[String]) -> String {
     format!("[{}]", items.join(","))
 }
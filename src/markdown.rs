@@ -0,0 +1,641 @@
+//! Structure lint and formatter for the SKILL.md body.
+//!
+//! The body is parsed with the `CommonMark` parser also used for references, so
+//! headings inside code blocks or HTML are not mistaken for structure.
+//! [`check_body_structure`] reports an unclear heading outline (several H1s,
+//! skipped levels, an H1 unrelated to the skill name, empty sections),
+//! whitespace noise, and `---` lines that look like a second frontmatter block.
+//! [`format_body`] normalizes the body's Markdown style for `fix`.
+
+use std::collections::BTreeMap;
+use std::ops::Range;
+
+use pulldown_cmark::{CodeBlockKind, Event, HeadingLevel, Parser, Tag, TagEnd};
+use serde::Deserialize;
+
+use crate::error::ValidationError;
+use crate::formatting::{body_with_offset, fenced_blocks};
+
+/// How `fix` formats the SKILL.md body.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct BodyFormat {
+    /// Whether to format the body at all. Frontmatter is always formatted.
+    pub body: bool,
+    /// Marker used for bullet list items.
+    pub list_marker: ListMarker,
+    /// Line endings written for the whole file.
+    pub line_endings: LineEndings,
+}
+
+impl Default for BodyFormat {
+    fn default() -> Self {
+        Self {
+            body: true,
+            list_marker: ListMarker::Dash,
+            line_endings: LineEndings::Lf,
+        }
+    }
+}
+
+/// Bullet list marker.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ListMarker {
+    /// `-`
+    Dash,
+    /// `*`
+    Asterisk,
+    /// `+`
+    Plus,
+}
+
+impl ListMarker {
+    const fn as_char(self) -> char {
+        match self {
+            Self::Dash => '-',
+            Self::Asterisk => '*',
+            Self::Plus => '+',
+        }
+    }
+}
+
+/// Line ending policy for files written by `fix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum LineEndings {
+    /// `\n`
+    Lf,
+    /// `\r\n`
+    Crlf,
+    /// Keep the file's existing line ending, judged by its first line.
+    Preserve,
+}
+
+impl LineEndings {
+    /// Rewrite `text`, which uses `\n`, with the line endings of this policy.
+    ///
+    /// `original` is the file content before fixing, used by [`Self::Preserve`].
+    pub fn apply(self, text: &str, original: &str) -> String {
+        let crlf = match self {
+            Self::Lf => false,
+            Self::Crlf => true,
+            Self::Preserve => original
+                .find('\n')
+                .is_some_and(|end| original[..end].ends_with('\r')),
+        };
+        if crlf {
+            text.replace('\n', "\r\n")
+        } else {
+            text.to_string()
+        }
+    }
+}
+
+struct Heading {
+    level: usize,
+    /// Byte offset of the heading in the body.
+    start: usize,
+    text: String,
+    has_content: bool,
+}
+
+/// Check the structure of the body of SKILL.md `content`.
+///
+/// `name` is the skill name, if known. Line numbers refer to `content`.
+pub fn check_body_structure(content: &str, name: Option<&str>) -> Vec<ValidationError> {
+    let (body, offset) = body_with_offset(content);
+    let line_at = |byte: usize| offset + body[..byte].matches('\n').count() + 1;
+    let mut errors = Vec::new();
+
+    let headings = headings(body);
+    let mut previous: Option<usize> = None;
+    let mut h1s = headings.iter().filter(|heading| heading.level == 1);
+    let first_h1 = h1s.next();
+    errors.extend(h1s.map(|heading| ValidationError::MultipleH1 {
+        line: line_at(heading.start),
+    }));
+
+    for (index, heading) in headings.iter().enumerate() {
+        if let Some(from) = previous.filter(|&from| heading.level > from + 1) {
+            errors.push(ValidationError::SkippedHeadingLevel {
+                line: line_at(heading.start),
+                from,
+                to: heading.level,
+            });
+        }
+        previous = Some(heading.level);
+
+        let next_level = headings.get(index + 1).map(|next| next.level);
+        if !heading.has_content && next_level.is_none_or(|next| next <= heading.level) {
+            errors.push(ValidationError::EmptySection {
+                line: line_at(heading.start),
+                heading: heading.text.clone(),
+            });
+        }
+    }
+
+    if let (Some(h1), Some(name)) = (first_h1, name.map(str::trim)) {
+        if !name.is_empty() && !relates_to_name(&h1.text, name) {
+            errors.push(ValidationError::HeadingUnrelatedToName {
+                heading: h1.text.clone(),
+                name: name.to_string(),
+            });
+        }
+    }
+
+    errors.extend(check_lines(body, offset));
+    errors.sort_by_key(error_line);
+    errors
+}
+
+/// Headings in document order, noting which have content before the next heading.
+fn headings(body: &str) -> Vec<Heading> {
+    let mut headings: Vec<Heading> = Vec::new();
+    let mut current: Option<Heading> = None;
+
+    for (event, range) in Parser::new(body).into_offset_iter() {
+        match event {
+            Event::Start(Tag::Heading { level, .. }) => {
+                current = Some(Heading {
+                    level: heading_level(level),
+                    start: range.start,
+                    text: String::new(),
+                    has_content: false,
+                });
+            }
+            Event::End(TagEnd::Heading(_)) => {
+                if let Some(mut heading) = current.take() {
+                    heading.text = heading.text.trim().to_string();
+                    headings.push(heading);
+                }
+            }
+            Event::Text(text) | Event::Code(text) => {
+                if let Some(heading) = &mut current {
+                    heading.text.push_str(&text);
+                } else if let Some(last) = headings.last_mut() {
+                    last.has_content = true;
+                }
+            }
+            Event::Start(_) | Event::Html(_) | Event::Rule | Event::InlineHtml(_)
+                if current.is_none() =>
+            {
+                if let Some(last) = headings.last_mut() {
+                    last.has_content = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    headings
+}
+
+const fn heading_level(level: HeadingLevel) -> usize {
+    match level {
+        HeadingLevel::H1 => 1,
+        HeadingLevel::H2 => 2,
+        HeadingLevel::H3 => 3,
+        HeadingLevel::H4 => 4,
+        HeadingLevel::H5 => 5,
+        HeadingLevel::H6 => 6,
+    }
+}
+
+/// Whether an H1 mentions the skill name or one of its significant words.
+fn relates_to_name(heading: &str, name: &str) -> bool {
+    let words = |text: &str| -> Vec<String> {
+        text.to_lowercase()
+            .split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(|word| word.strip_suffix('s').unwrap_or(word).to_string())
+            .collect()
+    };
+    let heading_words = words(heading);
+    let joined: String = heading_words.concat();
+    let name_words = words(name);
+    joined.contains(&name_words.concat())
+        || name_words
+            .iter()
+            .filter(|word| word.chars().count() >= 3)
+            .any(|word| heading_words.contains(word))
+}
+
+/// Line ranges (from 1, inclusive) of fenced code blocks, fences included.
+fn code_line_ranges(body: &str) -> Vec<(usize, usize)> {
+    fenced_blocks(body)
+        .iter()
+        .map(|block| {
+            let end = block.line + block.content.lines().count() + usize::from(block.closed);
+            (block.line, end)
+        })
+        .collect()
+}
+
+/// Line-based checks: trailing whitespace, hard tabs and stray frontmatter.
+fn check_lines(body: &str, offset: usize) -> Vec<ValidationError> {
+    let code_lines = code_line_ranges(body);
+    let in_code = |line: usize| {
+        code_lines
+            .iter()
+            .any(|&(start, end)| (start..=end).contains(&line))
+    };
+
+    let lines: Vec<&str> = body
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    // `fix` keeps indented code and HTML blocks as written, so whitespace in
+    // them is not reported.
+    let verbatim = BodyLayout::new(&lines.join("\n"), lines.len()).verbatim;
+    let mut errors = Vec::new();
+    for (index, text) in lines.iter().enumerate() {
+        let body_line = index + 1;
+        let line = offset + body_line;
+
+        if in_code(body_line) {
+            continue;
+        }
+        if !verbatim[index] && text.ends_with([' ', '\t']) && !text.trim().is_empty() {
+            errors.push(ValidationError::TrailingWhitespace { line });
+        }
+        if text.contains('\t') {
+            errors.push(ValidationError::HardTab { line });
+        }
+        if text.trim_end() == "---" && looks_like_frontmatter(&lines[index + 1..]) {
+            errors.push(ValidationError::StrayFrontmatter { line });
+        }
+    }
+    errors
+}
+
+/// Whether the lines after a `---` read like YAML keys closed by another `---`.
+fn looks_like_frontmatter(rest: &[&str]) -> bool {
+    let mut keys = 0;
+    for line in rest {
+        let line = line.trim_end();
+        if line == "---" {
+            return keys > 0;
+        }
+        let is_key = line.split_once(':').is_some_and(|(key, _)| {
+            !key.is_empty()
+                && key
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        });
+        if is_key {
+            keys += 1;
+        } else if !line.starts_with([' ', '-']) && !line.is_empty() {
+            return false;
+        }
+    }
+    false
+}
+
+/// Format a trimmed body.
+///
+/// This converts setext headings to ATX headings, puts blank lines around
+/// headings and fenced code blocks, collapses runs of blank lines, uses one
+/// bullet list marker, and removes trailing whitespace, turning a
+/// trailing-space hard line break into a backslash.
+///
+/// Code blocks and HTML blocks are left untouched. Inside block quotes and
+/// list items no blank lines are added, since they could make a tight list
+/// loose. The result has no trailing newline and always uses `\n`.
+pub fn format_body(body: &str, format: &BodyFormat) -> String {
+    let lines: Vec<&str> = body
+        .lines()
+        .map(|line| line.strip_suffix('\r').unwrap_or(line))
+        .collect();
+    let normalized = lines.join("\n");
+    let layout = BodyLayout::new(&normalized, lines.len());
+
+    let mut out = BodyWriter::default();
+    let mut index = 0;
+    while index < lines.len() {
+        let line = lines[index];
+        if let Some(&end) = layout.fences.get(&index) {
+            out.push(line.trim_end(), true, false);
+            // Code block contents go through verbatim, blank lines included.
+            for line in &lines[index + 1..=end] {
+                out.lines.push((*line).to_string());
+            }
+            if let Some(closing) = out.lines.last_mut().filter(|line| is_fence(line)) {
+                *closing = closing.trim_end().to_string();
+            }
+            out.blank_after = true;
+            index = end + 1;
+            continue;
+        }
+
+        if let Some((end, heading)) = layout.setext.get(&index) {
+            out.push(heading, true, true);
+            index = end + 1;
+            continue;
+        }
+
+        if layout.verbatim[index] {
+            out.push_verbatim(line);
+        } else {
+            let mut text = line.to_string();
+            for &(_, at) in layout.markers.iter().filter(|(line, _)| *line == index) {
+                if matches!(text.as_bytes().get(at), Some(b'-' | b'*' | b'+')) {
+                    text.replace_range(at..=at, &format.list_marker.as_char().to_string());
+                }
+            }
+            text.truncate(text.trim_end().len());
+            if layout.hard_breaks.contains(&index) {
+                text.push('\\');
+            }
+            let heading = layout.headings.contains(&index);
+            out.push(&text, heading, heading);
+        }
+        index += 1;
+    }
+
+    while out.lines.last().is_some_and(String::is_empty) {
+        out.lines.pop();
+    }
+    out.lines.join("\n")
+}
+
+/// What [`format_body`] may change on each line, read from the parser events.
+///
+/// Lines are numbered from 0.
+struct BodyLayout {
+    /// Lines of code blocks and HTML blocks, kept exactly as written.
+    verbatim: Vec<bool>,
+    /// Top-level fenced code blocks, by first line, with their last line.
+    fences: BTreeMap<usize, usize>,
+    /// Top-level ATX headings.
+    headings: Vec<usize>,
+    /// Top-level setext headings, by first line, with the underline line and
+    /// the ATX replacement.
+    setext: BTreeMap<usize, (usize, String)>,
+    /// Bullet list markers as line and byte column.
+    markers: Vec<(usize, usize)>,
+    /// Lines ending in a hard line break written as trailing spaces.
+    hard_breaks: Vec<usize>,
+}
+
+impl BodyLayout {
+    fn new(body: &str, line_count: usize) -> Self {
+        let line_starts: Vec<usize> = std::iter::once(0)
+            .chain(body.match_indices('\n').map(|(at, _)| at + 1))
+            .collect();
+        let line_at = |byte: usize| line_starts.partition_point(|&start| start <= byte) - 1;
+        let lines_of = |range: &Range<usize>| {
+            line_at(range.start)..=line_at(range.end.saturating_sub(1).max(range.start))
+        };
+
+        let mut layout = Self {
+            verbatim: vec![false; line_count],
+            fences: BTreeMap::new(),
+            headings: Vec::new(),
+            setext: BTreeMap::new(),
+            markers: Vec::new(),
+            hard_breaks: Vec::new(),
+        };
+        let mut in_container = vec![false; line_count];
+        let mut unordered: Vec<bool> = Vec::new();
+
+        for (event, range) in Parser::new(body).into_offset_iter() {
+            let lines = lines_of(&range);
+            let top_level = !in_container[*lines.start()];
+            match event {
+                Event::Start(Tag::BlockQuote(_)) => in_container[lines].fill(true),
+                Event::Start(Tag::List(first)) => {
+                    in_container[lines].fill(true);
+                    unordered.push(first.is_none());
+                }
+                Event::End(TagEnd::List(_)) => {
+                    unordered.pop();
+                }
+                Event::Start(Tag::Item) if unordered.last() == Some(&true) => {
+                    let item = &body[range.start..];
+                    let indent = item.len() - item.trim_start_matches([' ', '\t']).len();
+                    let line = line_at(range.start);
+                    layout
+                        .markers
+                        .push((line, range.start + indent - line_starts[line]));
+                }
+                Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(_))) if top_level => {
+                    layout.fences.insert(*lines.start(), *lines.end());
+                }
+                Event::Start(Tag::CodeBlock(_) | Tag::HtmlBlock) => {
+                    layout.verbatim[lines].fill(true);
+                }
+                Event::Start(Tag::Heading { level, .. }) if top_level => {
+                    let first = *lines.start();
+                    let source = body[range].trim_end();
+                    if !source.contains('\n') {
+                        layout.headings.push(first);
+                    } else if let Some((underline, heading)) = setext_heading(source, level) {
+                        layout.setext.insert(first, (first + underline, heading));
+                    }
+                }
+                Event::HardBreak if body[range.clone()].starts_with(' ') => {
+                    layout.hard_breaks.push(line_at(range.start));
+                }
+                _ => {}
+            }
+        }
+        layout
+    }
+}
+
+/// The underline's line, relative to the heading, and the ATX replacement of
+/// a setext heading.
+///
+/// A `---` underline under a `key: value` line is left alone; it is more
+/// likely a stray frontmatter block than a heading.
+fn setext_heading(source: &str, level: HeadingLevel) -> Option<(usize, String)> {
+    let (text, underline) = source.rsplit_once('\n')?;
+    let underline = underline.trim();
+    if !(underline.chars().all(|c| c == '=') || underline.chars().all(|c| c == '-')) {
+        return None;
+    }
+    let first = text.lines().next().unwrap_or_default();
+    if underline.starts_with('-') && looks_like_frontmatter(&[first, "---"]) {
+        return None;
+    }
+    let text: Vec<&str> = text.lines().map(str::trim).collect();
+    Some((
+        text.len(),
+        format!("{} {}", "#".repeat(heading_level(level)), text.join(" ")),
+    ))
+}
+
+/// Whether a line is a code fence without an info string.
+fn is_fence(line: &str) -> bool {
+    let fence = line.trim();
+    fence.len() >= 3 && (fence.chars().all(|c| c == '`') || fence.chars().all(|c| c == '~'))
+}
+
+/// Output lines of [`format_body`], collapsing blank lines it inserts.
+#[derive(Default)]
+struct BodyWriter {
+    lines: Vec<String>,
+    /// Whether the next non-blank line must be preceded by a blank line.
+    blank_after: bool,
+}
+
+impl BodyWriter {
+    fn push(&mut self, line: &str, blank_before: bool, blank_after: bool) {
+        let last_blank = self.lines.last().is_none_or(String::is_empty);
+        if line.trim().is_empty() {
+            if !last_blank {
+                self.lines.push(String::new());
+            }
+        } else {
+            if !last_blank && (blank_before || self.blank_after) {
+                self.lines.push(String::new());
+            }
+            self.lines.push(line.to_string());
+        }
+        self.blank_after = blank_after;
+    }
+
+    /// Push a line of a code or HTML block exactly as written.
+    fn push_verbatim(&mut self, line: &str) {
+        let last_blank = self.lines.last().is_none_or(String::is_empty);
+        if self.blank_after && !last_blank && !line.trim().is_empty() {
+            self.lines.push(String::new());
+        }
+        self.lines.push(line.to_string());
+        self.blank_after = false;
+    }
+}
+
+const fn error_line(error: &ValidationError) -> usize {
+    match error {
+        ValidationError::MultipleH1 { line }
+        | ValidationError::SkippedHeadingLevel { line, .. }
+        | ValidationError::EmptySection { line, .. }
+        | ValidationError::TrailingWhitespace { line }
+        | ValidationError::HardTab { line }
+        | ValidationError::StrayFrontmatter { line } => *line,
+        _ => 0,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn codes(body: &str, name: Option<&str>) -> Vec<(&'static str, usize)> {
+        let content = format!("---\nname: x\n---\n{body}");
+        check_body_structure(&content, name)
+            .iter()
+            .map(|error| (error.code(), error_line(error)))
+            .collect()
+    }
+
+    #[test]
+    fn accepts_well_structured_body() {
+        let body = "# PDF tools\n\nIntro.\n\n## Usage\n\n### Extract\n\nRun it.\n\n\
+                    ```make\nall:\n\tcc main.c\n```\n\n---\n\nDone.\n";
+        assert!(codes(body, Some("pdf-tools")).is_empty());
+    }
+
+    #[test]
+    fn reports_heading_problems() {
+        let body = "# Overview\n\nText.\n\n#### Deep\n\nText.\n\n## Empty\n\n## Last\n\nText.\n\n# Again\n\nText.\n";
+        assert_eq!(
+            codes(body, Some("pdf-tools")),
+            [
+                ("heading-unrelated-to-name", 0),
+                ("skipped-heading-level", 8),
+                ("empty-section", 12),
+                ("multiple-h1", 18),
+            ]
+        );
+        assert!(codes(
+            "# Code in `#` headings\n\n```md\n# not a heading\n```\n",
+            None
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn relates_heading_to_name() {
+        assert!(relates_to_name("PDF Tools", "pdf-tools"));
+        assert!(relates_to_name("Working with PDFs", "pdf-tools"));
+        assert!(relates_to_name("Skill: SQLFormat", "sql-format"));
+        assert!(!relates_to_name("Overview", "pdf-tools"));
+    }
+
+    #[test]
+    fn reports_whitespace_and_stray_frontmatter() {
+        let body =
+            "# X\n\nTrailing  \nTab\there\n\n---\nname: other\ndescription: dup\n---\n\nText.\n";
+        assert_eq!(
+            codes(body, None),
+            [
+                ("trailing-whitespace", 6),
+                ("hard-tab", 7),
+                ("stray-frontmatter", 9),
+            ]
+        );
+    }
+
+    #[test]
+    fn formats_body() {
+        let body = "Title\n=====\nIntro line  \nnext line\t\n* one\n+ two\n  * nested\n\n\n\n\
+                    ## Usage ##\n```bash\necho hi   \n\n\n```\nAfter\n\n* * *\n\nname: x\n---";
+        assert_eq!(
+            format_body(body, &BodyFormat::default()),
+            "# Title\n\nIntro line\\\nnext line\n- one\n- two\n  - nested\n\n\
+             ## Usage ##\n\n```bash\necho hi   \n\n\n```\n\nAfter\n\n* * *\n\nname: x\n---"
+        );
+    }
+
+    #[test]
+    fn formatted_body_has_no_trailing_whitespace() {
+        let body = "# X\n\nIntro  \nnext\t\n\n```bash\necho hi   \n```\n\n    indented  \n";
+        let formatted = format_body(body, &BodyFormat::default());
+        assert!(formatted.contains("echo hi   \n"));
+        assert!(!codes(&formatted, None)
+            .iter()
+            .any(|(code, _)| *code == "trailing-whitespace"));
+    }
+
+    #[test]
+    fn formatting_leaves_code_html_and_containers_alone() {
+        for body in [
+            "Intro.\n\n    * also not\n    trailing  \n\nAfter.",
+            "<details>\n* html content\n# raw\n</details>",
+            "> ```\n> code  \n> ```",
+            "1. Step\n   ```bash\n   run\n   ```\n2. Next",
+            "- Item\n  # Heading\n- Other",
+        ] {
+            assert_eq!(format_body(body, &BodyFormat::default()), body);
+        }
+        assert_eq!(
+            format_body("> * quoted\n>   + nested", &BodyFormat::default()),
+            "> - quoted\n>   - nested"
+        );
+    }
+
+    #[test]
+    fn formatting_is_idempotent_and_configurable() {
+        let format = BodyFormat {
+            list_marker: ListMarker::Asterisk,
+            ..BodyFormat::default()
+        };
+        let once = format_body("# A\n- x\n- y\n~~~\ncode\n~~~", &format);
+        assert_eq!(once, "# A\n\n* x\n* y\n\n~~~\ncode\n~~~");
+        assert_eq!(format_body(&once, &format), once);
+    }
+
+    #[test]
+    fn applies_line_ending_policy() {
+        assert_eq!(LineEndings::Lf.apply("a\nb\n", "a\r\nb\r\n"), "a\nb\n");
+        assert_eq!(LineEndings::Crlf.apply("a\nb\n", ""), "a\r\nb\r\n");
+        assert_eq!(
+            LineEndings::Preserve.apply("a\nb\n", "x\r\ny\n"),
+            "a\r\nb\r\n"
+        );
+        assert_eq!(LineEndings::Preserve.apply("a\nb\n", "x\ny"), "a\nb\n");
+    }
+}
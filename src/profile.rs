@@ -0,0 +1,132 @@
+//! Spec profiles for different agent runtimes.
+//!
+//! A profile determines which frontmatter fields are allowed and the order
+//! in which `fix` writes fields. Length limits are the same for every
+//! profile. The default [`Profile::Strict`] follows the open Agent Skills
+//! spec.
+
+use crate::config::{FieldKind, FieldSpec};
+use crate::skill::{ALLOWED_FIELDS, FIELD_ORDER};
+
+/// Frontmatter fields accepted by Claude Code.
+const CLAUDE_CODE_FIELDS: [&str; 10] = [
+    "name",
+    "description",
+    "license",
+    "allowed-tools",
+    "metadata",
+    "compatibility",
+    "model",
+    "disable-model-invocation",
+    "user-invocable",
+    "argument-hint",
+];
+
+/// Field order used when formatting Claude Code skills.
+const CLAUDE_CODE_FIELD_ORDER: [&str; 10] = [
+    "name",
+    "description",
+    "license",
+    "compatibility",
+    "allowed-tools",
+    "model",
+    "argument-hint",
+    "disable-model-invocation",
+    "user-invocable",
+    "metadata",
+];
+
+/// A set of frontmatter rules targeting a specific agent runtime.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Profile {
+    /// The open Agent Skills spec.
+    #[default]
+    Strict,
+    /// Claude Code, which accepts additional invocation-related fields.
+    ClaudeCode,
+}
+
+impl Profile {
+    /// All available profiles.
+    pub const ALL: [Self; 2] = [Self::Strict, Self::ClaudeCode];
+
+    /// The name used to select this profile in config and on the command line.
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Strict => "strict",
+            Self::ClaudeCode => "claude-code",
+        }
+    }
+
+    /// Look up a profile by name.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|profile| profile.name() == name)
+    }
+
+    /// Fields allowed in frontmatter.
+    pub const fn allowed_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::Strict => &ALLOWED_FIELDS,
+            Self::ClaudeCode => &CLAUDE_CODE_FIELDS,
+        }
+    }
+
+    /// Order in which fields are written in formatted output.
+    pub const fn field_order(self) -> &'static [&'static str] {
+        match self {
+            Self::Strict => &FIELD_ORDER,
+            Self::ClaudeCode => &CLAUDE_CODE_FIELD_ORDER,
+        }
+    }
+
+    /// Typed schemas for the runtime-specific fields this profile adds.
+    pub fn field_specs(self) -> Vec<FieldSpec> {
+        let spec = |name: &str, kind: FieldKind| FieldSpec {
+            name: name.to_string(),
+            kind,
+            required: false,
+            max_length: None,
+        };
+        match self {
+            Self::Strict => Vec::new(),
+            Self::ClaudeCode => vec![
+                spec("model", FieldKind::String { pattern: None }),
+                spec("argument-hint", FieldKind::String { pattern: None }),
+                spec("disable-model-invocation", FieldKind::Bool),
+                spec("user-invocable", FieldKind::Bool),
+            ],
+        }
+    }
+
+    /// Whether `field` is allowed by this profile.
+    pub fn allows(self, field: &str) -> bool {
+        self.allowed_fields().contains(&field)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn profile_names_round_trip() {
+        for profile in Profile::ALL {
+            assert_eq!(Profile::from_name(profile.name()), Some(profile));
+        }
+        assert_eq!(Profile::from_name("unknown"), None);
+    }
+
+    #[test]
+    fn field_order_covers_allowed_fields() {
+        for profile in Profile::ALL {
+            let mut allowed = profile.allowed_fields().to_vec();
+            let mut order = profile.field_order().to_vec();
+            allowed.sort_unstable();
+            order.sort_unstable();
+            assert_eq!(allowed, order, "{}", profile.name());
+            for spec in profile.field_specs() {
+                assert!(profile.allows(&spec.name), "{}", spec.name);
+            }
+        }
+    }
+}
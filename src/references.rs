@@ -0,0 +1,497 @@
+//! Validation of references from a skill body to bundled files.
+//!
+//! Skills link to files in `scripts/`, `references/` and `assets/` using
+//! Markdown links, images, or inline code such as `` `scripts/run.py` ``.
+//! These references are resolved against the skill directory and reported if
+//! the target is missing, absolute, or escapes the skill directory.
+//!
+//! The inverse check, [`find_orphaned_files`], reports bundled files that are
+//! never referenced, directly or through referenced Markdown files.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use pulldown_cmark::{Event, Parser, Tag, TagEnd};
+use walkdir::WalkDir;
+
+use crate::error::ValidationError;
+
+/// Directories that conventionally hold bundled skill resources.
+pub const BUNDLED_DIRS: [&str; 3] = ["scripts", "references", "assets"];
+
+/// A local file referenced from Markdown.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Reference {
+    /// The target path as written, without any `#fragment` or `?query`.
+    pub target: String,
+    /// How the reference was written.
+    pub kind: ReferenceKind,
+}
+
+/// The Markdown construct a reference came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceKind {
+    /// `[text](target)`
+    Link,
+    /// `![alt](target)`
+    Image,
+    /// `` `scripts/tool.py` ``
+    InlineCode,
+}
+
+/// Extract local file references from Markdown.
+///
+/// URLs with a scheme (`https:`, `mailto:`, ...) and pure `#anchor` links are
+/// skipped. Inline code only counts as a reference when a word in it is a
+/// path into a bundled directory (`scripts/`, `references/`, `assets/`),
+/// optionally after `./` or `../`, or a `../` path with a file extension, and
+/// is not a glob or placeholder. Each target is returned once, in order of
+/// first appearance.
+pub fn extract_references(markdown: &str) -> Vec<Reference> {
+    let mut references: Vec<Reference> = Vec::new();
+    let mut push = |target: String, kind: ReferenceKind| {
+        if !references.iter().any(|existing| existing.target == target) {
+            references.push(Reference { target, kind });
+        }
+    };
+
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::Link { dest_url, .. }) => {
+                if let Some(target) = local_target(&dest_url) {
+                    push(target, ReferenceKind::Link);
+                }
+            }
+            Event::Start(Tag::Image { dest_url, .. }) => {
+                if let Some(target) = local_target(&dest_url) {
+                    push(target, ReferenceKind::Image);
+                }
+            }
+            Event::Code(code) => {
+                for word in code.split_whitespace() {
+                    let word = word.trim_matches(['"', '\'']);
+                    if looks_like_bundled_path(word) {
+                        push(word.to_string(), ReferenceKind::InlineCode);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    references
+}
+
+/// Validate the local file references in a skill body.
+pub fn validate_references(body: &str, skill_dir: &Path) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    for reference in extract_references(body) {
+        match resolve_reference(skill_dir, &reference.target) {
+            Ok(path) => {
+                if !path.exists() {
+                    errors.push(ValidationError::MissingReference(reference.target));
+                }
+            }
+            Err(ReferenceProblem::Absolute) => {
+                errors.push(ValidationError::AbsoluteReference(reference.target));
+            }
+            Err(ReferenceProblem::EscapesSkillDir) => {
+                errors.push(ValidationError::ReferenceEscapesSkillDir(reference.target));
+            }
+        }
+    }
+
+    errors
+}
+
+/// Find files under the bundled directories that SKILL.md never references.
+///
+/// References are followed transitively through referenced Markdown files,
+/// whose links resolve relative to their own location. Paths in code blocks,
+/// such as `python scripts/run.py` in a shell example, count as references.
+/// Referencing a directory counts as referencing every file in it. Returned
+/// paths are relative to the skill directory and use `/` separators.
+pub fn find_orphaned_files(skill_dir: &Path, body: &str) -> Vec<String> {
+    let referenced = referenced_paths(skill_dir, body);
+    let mut orphans = Vec::new();
+
+    for dir in BUNDLED_DIRS {
+        let root = skill_dir.join(dir);
+        if !root.is_dir() {
+            continue;
+        }
+        for entry in WalkDir::new(&root)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_map(Result::ok)
+        {
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let path = entry.path();
+            if referenced.iter().any(|target| path.starts_with(target)) {
+                continue;
+            }
+            if let Ok(rel) = path.strip_prefix(skill_dir) {
+                orphans.push(relative_display(rel));
+            }
+        }
+    }
+
+    orphans
+}
+
+/// Markdown files reachable from `body` through references, sorted by path.
+pub(crate) fn referenced_markdown_files(skill_dir: &Path, body: &str) -> Vec<PathBuf> {
+    referenced_paths(skill_dir, body)
+        .into_iter()
+        .filter(|path| is_markdown(path) && path.is_file())
+        .collect()
+}
+
+/// Files reachable from `body` through references, sorted by path.
+///
+/// Paths in code blocks count as references. A referenced directory
+/// contributes every file inside it.
+pub(crate) fn referenced_files(skill_dir: &Path, body: &str) -> Vec<PathBuf> {
+    let mut files = BTreeSet::new();
+    for path in referenced_paths(skill_dir, body) {
+        if path.is_dir() {
+            files.extend(
+                WalkDir::new(&path)
+                    .into_iter()
+                    .filter_map(Result::ok)
+                    .filter(|entry| entry.file_type().is_file())
+                    .map(walkdir::DirEntry::into_path),
+            );
+        } else if path.is_file() {
+            files.insert(path);
+        }
+    }
+    files.into_iter().collect()
+}
+
+/// Resolve every path reachable from `body` through references.
+fn referenced_paths(skill_dir: &Path, body: &str) -> BTreeSet<PathBuf> {
+    let mut seen = BTreeSet::new();
+    let mut queue: Vec<(PathBuf, String)> = vec![(PathBuf::new(), body.to_string())];
+
+    while let Some((base, markdown)) = queue.pop() {
+        // Code paths are conventionally relative to the skill root; links and
+        // images are relative to the file they appear in.
+        let targets = extract_references(&markdown)
+            .into_iter()
+            .map(|reference| match reference.kind {
+                ReferenceKind::InlineCode => reference.target,
+                ReferenceKind::Link | ReferenceKind::Image => {
+                    base.join(&reference.target).to_string_lossy().into_owned()
+                }
+            })
+            .chain(code_block_paths(&markdown));
+        for target in targets {
+            let Ok(path) = resolve_reference(skill_dir, &target) else {
+                continue;
+            };
+            if !seen.insert(path.clone()) {
+                continue;
+            }
+            if is_markdown(&path) {
+                if let Ok(content) = fs::read_to_string(&path) {
+                    let dir = path
+                        .parent()
+                        .and_then(|parent| parent.strip_prefix(skill_dir).ok())
+                        .map(Path::to_path_buf)
+                        .unwrap_or_default();
+                    queue.push((dir, content));
+                }
+            }
+        }
+    }
+
+    seen
+}
+
+/// Bundled paths mentioned in code blocks.
+///
+/// These are not validated, since examples may show files the reader creates,
+/// but they keep the files they name from being reported as orphaned.
+fn code_block_paths(markdown: &str) -> Vec<String> {
+    let mut paths = Vec::new();
+    let mut in_code_block = false;
+    for event in Parser::new(markdown) {
+        match event {
+            Event::Start(Tag::CodeBlock(_)) => in_code_block = true,
+            Event::End(TagEnd::CodeBlock) => in_code_block = false,
+            Event::Text(text) if in_code_block => {
+                for word in text.split_whitespace() {
+                    let word = word.trim_matches(['"', '\'']);
+                    if looks_like_bundled_path(word) {
+                        paths.push(word.to_string());
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+    paths
+}
+
+fn is_markdown(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md"))
+}
+
+pub(crate) fn relative_display(path: &Path) -> String {
+    path.components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/")
+}
+
+/// Why a reference cannot be resolved inside the skill directory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReferenceProblem {
+    /// The target is an absolute path.
+    Absolute,
+    /// The target uses `..` to leave the skill directory.
+    EscapesSkillDir,
+}
+
+/// Resolve a reference target against a skill directory.
+///
+/// The path is normalized lexically, without touching the filesystem.
+pub fn resolve_reference(skill_dir: &Path, target: &str) -> Result<PathBuf, ReferenceProblem> {
+    if is_absolute(target) {
+        return Err(ReferenceProblem::Absolute);
+    }
+
+    let mut parts: Vec<&std::ffi::OsStr> = Vec::new();
+    for component in Path::new(target).components() {
+        match component {
+            Component::Normal(part) => parts.push(part),
+            Component::ParentDir => {
+                if parts.pop().is_none() {
+                    return Err(ReferenceProblem::EscapesSkillDir);
+                }
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return Err(ReferenceProblem::Absolute),
+        }
+    }
+
+    let mut path = skill_dir.to_path_buf();
+    path.extend(parts);
+    Ok(path)
+}
+
+fn is_absolute(target: &str) -> bool {
+    let bytes = target.as_bytes();
+    target.starts_with('/')
+        || target.starts_with('\\')
+        || target.starts_with('~')
+        || (bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':')
+}
+
+fn local_target(dest: &str) -> Option<String> {
+    let dest = dest.trim();
+    if dest.is_empty() || dest.starts_with('#') {
+        return None;
+    }
+    if dest.starts_with("file:") {
+        return Some(percent_decode(dest.trim_start_matches("file:")));
+    }
+    if has_url_scheme(dest) {
+        return None;
+    }
+
+    let end = dest.find(['#', '?']).unwrap_or(dest.len());
+    let path = &dest[..end];
+    (!path.is_empty()).then(|| percent_decode(path))
+}
+
+fn has_url_scheme(dest: &str) -> bool {
+    let Some((scheme, _)) = dest.split_once(':') else {
+        return false;
+    };
+    // A single letter is a Windows drive, not a scheme.
+    scheme.len() > 1
+        && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+        && scheme
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'))
+}
+
+fn looks_like_bundled_path(word: &str) -> bool {
+    // Globs and placeholders such as `scripts/*.py` or `assets/<name>.png`
+    // describe a family of files rather than a specific one.
+    if word.contains(['*', '?', '{', '}', '<', '>', '$']) {
+        return false;
+    }
+    let mut path = word.strip_prefix("./").unwrap_or(word);
+    let mut parent = false;
+    while let Some(rest) = path.strip_prefix("../") {
+        path = rest;
+        parent = true;
+    }
+    let in_bundled_dir = BUNDLED_DIRS.iter().any(|dir| {
+        path.strip_prefix(dir)
+            .and_then(|rest| rest.strip_prefix('/'))
+            .is_some_and(|rest| !rest.is_empty())
+    });
+    // Words like `../other-repo` or `cd ../` are prose about other
+    // directories; a `../` path needs a file extension to count.
+    in_bundled_dir || (parent && has_extension(path))
+}
+
+fn has_extension(path: &str) -> bool {
+    Path::new(path)
+        .extension()
+        .is_some_and(|extension| !extension.is_empty())
+}
+
+fn percent_decode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8(out).unwrap_or_else(|_| text.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn targets(markdown: &str) -> Vec<String> {
+        extract_references(markdown)
+            .into_iter()
+            .map(|reference| reference.target)
+            .collect()
+    }
+
+    #[test]
+    fn extracts_links_images_and_inline_code() {
+        let body = "\
+See [the guide](references/guide.md#setup) and ![diagram](assets/flow%20chart.png).
+Run `python scripts/run.py --fast` or `./scripts/other.sh`.
+Visit [site](https://example.com), [anchor](#usage), [mail](mailto:a@b.c).
+Not a path: `scripts` or `echo hi` or `scripts/*.py` or `assets/<name>.png`.
+Elsewhere: `cd ../` or `../other-repo`, but `../shared/notes.md` is a path.
+Again [guide](references/guide.md).
+
+```bash
+python scripts/in_fence.py
+```
+";
+        assert_eq!(
+            targets(body),
+            [
+                "references/guide.md",
+                "assets/flow chart.png",
+                "scripts/run.py",
+                "./scripts/other.sh",
+                "../shared/notes.md",
+            ]
+        );
+    }
+
+    #[test]
+    fn finds_orphaned_files_transitively() {
+        let dir = TempDir::new().expect("temp dir");
+        let root = dir.path();
+        for sub in ["scripts", "references", "assets/icons"] {
+            fs::create_dir_all(root.join(sub)).expect("mkdir");
+        }
+        fs::write(
+            root.join("references").join("guide.md"),
+            "Run [helper](../scripts/helper.sh) or `scripts/tool.py`.\n",
+        )
+        .expect("write");
+        for file in [
+            "scripts/helper.sh",
+            "scripts/tool.py",
+            "scripts/unused.py",
+            "assets/icons/a.png",
+            "assets/icons/b.png",
+            "assets/logo.png",
+        ] {
+            fs::write(root.join(file), "").expect("write");
+        }
+
+        let body = "See [guide](references/guide.md) and [icons](assets/icons/).\n";
+        assert_eq!(
+            find_orphaned_files(root, body),
+            ["scripts/unused.py", "assets/logo.png"]
+        );
+    }
+
+    #[test]
+    fn paths_in_code_blocks_are_not_orphaned() {
+        let dir = TempDir::new().expect("temp dir");
+        let root = dir.path();
+        fs::create_dir_all(root.join("scripts")).expect("mkdir");
+        for file in ["scripts/run.py", "scripts/unused.py"] {
+            fs::write(root.join(file), "").expect("write");
+        }
+
+        let body = "Run it:\n\n```bash\npython scripts/run.py --fast\n```\n";
+        assert_eq!(find_orphaned_files(root, body), ["scripts/unused.py"]);
+    }
+
+    #[test]
+    fn resolves_relative_paths_lexically() {
+        let dir = Path::new("/skills/demo");
+        assert_eq!(
+            resolve_reference(dir, "./references/../scripts/a.py"),
+            Ok(dir.join("scripts").join("a.py"))
+        );
+        assert_eq!(
+            resolve_reference(dir, "../other/SKILL.md"),
+            Err(ReferenceProblem::EscapesSkillDir)
+        );
+        assert_eq!(
+            resolve_reference(dir, "/etc/passwd"),
+            Err(ReferenceProblem::Absolute)
+        );
+        assert_eq!(
+            resolve_reference(dir, "C:\\tools\\x.exe"),
+            Err(ReferenceProblem::Absolute)
+        );
+    }
+
+    #[test]
+    fn reports_missing_absolute_and_escaping_references() {
+        let dir = TempDir::new().expect("temp dir");
+        fs::create_dir_all(dir.path().join("scripts")).expect("mkdir");
+        fs::write(dir.path().join("scripts").join("ok.py"), "").expect("write");
+
+        let body = "\
+[ok](scripts/ok.py) [missing](references/missing.md)
+[abs](/usr/share/doc.md) [up](../secrets.txt)
+";
+        let errors = validate_references(body, dir.path());
+        assert_eq!(
+            errors,
+            [
+                ValidationError::MissingReference("references/missing.md".to_string()),
+                ValidationError::AbsoluteReference("/usr/share/doc.md".to_string()),
+                ValidationError::ReferenceEscapesSkillDir("../secrets.txt".to_string()),
+            ]
+        );
+    }
+}
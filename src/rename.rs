@@ -0,0 +1,427 @@
+//! Renaming skills.
+//!
+//! By default `fix` resolves a name that disagrees with its directory by
+//! rewriting the name, which is the wrong direction when a skill is meant to
+//! be renamed. [`plan_rename`] works out every edit a rename needs: the
+//! directory, the `name` field, and the `skill:` links and `metadata.related`
+//! entries of other skills that refer to the old name. [`apply_rename`]
+//! performs them.
+
+use std::collections::BTreeMap;
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+use pulldown_cmark::{Event, Parser, Tag};
+use serde_yaml::Value;
+
+use crate::catalog::skill_name;
+use crate::config::Config;
+use crate::discovery::get_dir_name;
+use crate::error::FixError;
+use crate::formatting::{
+    body_with_offset, format_frontmatter_with_config, format_scalar, parse_frontmatter,
+};
+use crate::graph::{link_target, RELATED_KEY, SKILL_LINK_SCHEME};
+use crate::markdown::LineEndings;
+use crate::skill::SkillFile;
+use crate::validation::validate_name;
+
+/// The edits that rename a skill.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RenamePlan {
+    /// The current skill directory.
+    pub old_dir: PathBuf,
+    /// The skill directory after the rename.
+    pub new_dir: PathBuf,
+    /// Files to rewrite, at their paths before the directory is renamed.
+    pub edits: Vec<FileEdit>,
+}
+
+/// A rewritten file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileEdit {
+    /// The file to rewrite.
+    pub path: PathBuf,
+    /// The current content.
+    pub original: String,
+    /// The new content.
+    pub content: String,
+}
+
+/// Plan renaming the skill named `old` to `new` within a catalog of skills.
+///
+/// The skill directory moves to a sibling named `new`. Frontmatter lines are
+/// edited in place where possible, so unrelated formatting is kept; the
+/// frontmatter is only reformatted when a value spans several lines.
+///
+/// # Errors
+///
+/// Returns an error if `new` is not a valid name, if no skill or several
+/// skills are named `old`, if another skill is already named `new`, if the
+/// new directory already exists, or if the renamed skill cannot be parsed.
+pub fn plan_rename(
+    skills: &[SkillFile],
+    old: &str,
+    new: &str,
+    config: &Config,
+) -> Result<RenamePlan, FixError> {
+    if let Some(error) = validate_name(new, None).into_iter().next() {
+        return Err(FixError::InvalidName {
+            name: new.to_string(),
+            reason: error.to_string(),
+        });
+    }
+
+    let names: Vec<String> = skills
+        .iter()
+        .map(|skill| match parse_frontmatter(&skill.content) {
+            Ok((metadata, _)) => skill_name(&metadata, skill),
+            Err(_) => get_dir_name(&skill.dir_path),
+        })
+        .collect();
+    let mut matches = skills.iter().zip(&names).filter(|(_, name)| *name == old);
+    let Some((target, _)) = matches.next() else {
+        return Err(FixError::UnknownSkill(old.to_string()));
+    };
+    if matches.next().is_some() {
+        return Err(FixError::AmbiguousSkill(old.to_string()));
+    }
+    if old != new && names.iter().any(|name| name == new) {
+        return Err(FixError::SkillExists(new.to_string()));
+    }
+
+    let old_dir = target.dir_path.clone();
+    let new_dir = old_dir.with_file_name(new);
+    if new_dir.exists() && !same_file(&old_dir, &new_dir) {
+        return Err(FixError::TargetExists(new_dir));
+    }
+
+    let mut edits = Vec::new();
+    for skill in skills {
+        let is_target = skill.file_path == target.file_path;
+        let content = match rewrite_skill(&skill.content, old, new, is_target, config) {
+            Ok(content) => content,
+            Err(err) if is_target => return Err(err),
+            // Skills that cannot be parsed hold no references to rewrite.
+            Err(_) => continue,
+        };
+        if content != skill.content {
+            edits.push(FileEdit {
+                path: skill.file_path.clone(),
+                original: skill.content.clone(),
+                content,
+            });
+        }
+    }
+
+    Ok(RenamePlan {
+        old_dir,
+        new_dir,
+        edits,
+    })
+}
+
+/// Write the edits of a rename, then rename the skill directory.
+///
+/// # Errors
+///
+/// Returns an error if a file cannot be written or the directory cannot be
+/// renamed. Edits already written are not rolled back.
+pub fn apply_rename(plan: &RenamePlan) -> Result<(), FixError> {
+    for edit in &plan.edits {
+        fs::write(&edit.path, &edit.content).map_err(|source| FixError::WriteFailed {
+            path: edit.path.clone(),
+            source,
+        })?;
+    }
+    if plan.old_dir != plan.new_dir {
+        rename_dir(&plan.old_dir, &plan.new_dir)?;
+    }
+    Ok(())
+}
+
+/// Rename a directory, including case-only renames on case-insensitive
+/// filesystems, which go through a temporary name.
+pub(crate) fn rename_dir(from: &Path, to: &Path) -> Result<(), FixError> {
+    let result = if to.exists() {
+        let temp = from.with_file_name(format!(".{}.rename", get_dir_name(from)));
+        fs::rename(from, &temp).and_then(|()| fs::rename(&temp, to))
+    } else {
+        fs::rename(from, to)
+    };
+    result.map_err(|source| FixError::RenameFailed {
+        path: from.to_path_buf(),
+        source,
+    })
+}
+
+/// Whether two existing paths are the same file, as on a case-insensitive
+/// filesystem where `Skill` and `skill` name one directory.
+pub(crate) fn same_file(a: &Path, b: &Path) -> bool {
+    match (a.canonicalize(), b.canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
+
+/// The SKILL.md `content` with references to `old` pointing to `new`, and
+/// with `name` set to `new` if this is the renamed skill.
+fn rewrite_skill(
+    content: &str,
+    old: &str,
+    new: &str,
+    is_target: bool,
+    config: &Config,
+) -> Result<String, FixError> {
+    let (metadata, _) = parse_frontmatter(content)?;
+    let mut expected = metadata.clone();
+    if is_target {
+        expected.insert("name".to_string(), Value::String(new.to_string()));
+    }
+    if let Some(Value::Mapping(map)) = expected.get_mut("metadata") {
+        if let Some(Value::String(related)) = map.get_mut(RELATED_KEY) {
+            *related = replace_related(related, old, new);
+        }
+    }
+
+    let (body, _) = body_with_offset(content);
+    let head = &content[..content.len() - body.len()];
+    let head = if expected == metadata {
+        head.to_string()
+    } else {
+        edit_frontmatter(head, &expected, old, new, is_target)
+            .map_or_else(|| reformat_frontmatter(head, &expected, config), Ok)?
+    };
+    Ok(format!("{head}{}", rewrite_links(body, old, new)))
+}
+
+/// Edit the `name` and `metadata.related` lines of a frontmatter block.
+///
+/// Returns `None` if the edited block does not parse to `expected`, for
+/// example when a value is a multi-line scalar.
+fn edit_frontmatter(
+    head: &str,
+    expected: &BTreeMap<String, Value>,
+    old: &str,
+    new: &str,
+    is_target: bool,
+) -> Option<String> {
+    let mut edited = String::with_capacity(head.len());
+    let mut in_metadata = false;
+    for line in head.split_inclusive('\n') {
+        let text = line.trim_end_matches(['\n', '\r']);
+        let ending = &line[text.len()..];
+        let indented = text.starts_with([' ', '\t']);
+        if !indented {
+            in_metadata = text.trim_end() == "metadata:";
+        }
+
+        if is_target && text.starts_with("name:") {
+            edited.push_str(&format!("name: {}{ending}", format_scalar(new)));
+        } else if let Some(value) = text
+            .trim_start()
+            .strip_prefix(RELATED_KEY)
+            .and_then(|rest| rest.strip_prefix(':'))
+            .filter(|_| in_metadata && indented)
+        {
+            let key = &text[..text.len() - value.len()];
+            let quote = value
+                .trim()
+                .chars()
+                .next()
+                .filter(|c| matches!(c, '"' | '\''));
+            let inner = match quote {
+                Some(quote) => value.trim().trim_matches(quote),
+                None => value.trim(),
+            };
+            let replaced = replace_related(inner, old, new);
+            let value = match quote {
+                Some(quote) => format!("{quote}{replaced}{quote}"),
+                None => replaced,
+            };
+            edited.push_str(&format!("{key} {value}{ending}"));
+        } else {
+            edited.push_str(line);
+        }
+    }
+
+    let (metadata, _) = parse_frontmatter(&edited).ok()?;
+    (metadata == *expected).then_some(edited)
+}
+
+fn reformat_frontmatter(
+    head: &str,
+    expected: &BTreeMap<String, Value>,
+    config: &Config,
+) -> Result<String, FixError> {
+    let formatted = format_frontmatter_with_config(expected, config)?;
+    let bom = if head.starts_with('\u{feff}') {
+        "\u{feff}"
+    } else {
+        ""
+    };
+    Ok(LineEndings::Preserve.apply(&format!("{bom}{formatted}\n"), head))
+}
+
+/// Replace `old` entries of a comma- or space-separated list with `new`.
+fn replace_related(related: &str, old: &str, new: &str) -> String {
+    related
+        .split_inclusive(|c: char| c == ',' || c.is_whitespace())
+        .map(|piece| {
+            let name = piece.trim_end_matches(|c: char| c == ',' || c.is_whitespace());
+            if name == old {
+                format!("{new}{}", &piece[name.len()..])
+            } else {
+                piece.to_string()
+            }
+        })
+        .collect()
+}
+
+/// Point `skill:` links to `old` in `body` at `new`.
+///
+/// Only link destinations are rewritten, so code blocks and prose that
+/// mention `skill:old` are left alone.
+fn rewrite_links(body: &str, old: &str, new: &str) -> String {
+    let mut parser = Parser::new(body).into_offset_iter();
+    let mut ranges: Vec<Range<usize>> = parser
+        .by_ref()
+        .filter_map(|(event, range)| match event {
+            Event::Start(Tag::Link { dest_url, .. }) if link_target(&dest_url) == Some(old) => {
+                Some(range)
+            }
+            _ => None,
+        })
+        .collect();
+    ranges.extend(
+        parser
+            .reference_definitions()
+            .iter()
+            .filter(|(_, definition)| link_target(&definition.dest) == Some(old))
+            .map(|(_, definition)| definition.span.clone()),
+    );
+    ranges.sort_by_key(|range| range.start);
+
+    let link = format!("{SKILL_LINK_SCHEME}{old}");
+    let mut rewritten = String::with_capacity(body.len());
+    let mut last = 0;
+    for range in ranges {
+        let mut start = range.start.max(last);
+        while let Some(found) = body[start..range.end].find(&link) {
+            let at = start + found;
+            let end = at + link.len();
+            let boundary = body[end..]
+                .chars()
+                .next()
+                .is_none_or(|c| !c.is_alphanumeric() && c != '-');
+            if boundary {
+                rewritten.push_str(&body[last..at]);
+                rewritten.push_str(SKILL_LINK_SCHEME);
+                rewritten.push_str(new);
+                last = end;
+            }
+            start = end;
+        }
+    }
+    rewritten.push_str(&body[last..]);
+    rewritten
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(dir: &Path, name: &str, content: &str) -> SkillFile {
+        let dir_path = dir.join(name);
+        fs::create_dir_all(&dir_path).expect("mkdir");
+        let file_path = dir_path.join("SKILL.md");
+        fs::write(&file_path, content).expect("write");
+        SkillFile {
+            dir_path,
+            file_path,
+            content: content.to_string(),
+        }
+    }
+
+    #[test]
+    fn renames_skill_and_rewrites_references() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skills = [
+            write(
+                dir.path(),
+                "pdf",
+                "---\nname: pdf\ndescription: Use when reading PDFs\n---\n\n# PDF\n\nSee [pdf](skill:pdf).\n",
+            ),
+            write(
+                dir.path(),
+                "docx",
+                "---\nname: docx\ndescription: Use when writing\nmetadata:\n  related: \"pdf, pdfx\"\n---\n\nUse [pdf](skill:pdf#usage), not `skill:pdf`, or [x][ref].\n\n[ref]: skill:pdf\n",
+            ),
+            write(
+                dir.path(),
+                "xlsx",
+                "---\nname: xlsx\ndescription: Use for sheets\n---\n\nSee [pdfx](skill:pdfx).\n",
+            ),
+        ];
+
+        let plan = plan_rename(&skills, "pdf", "pdf-tools", &Config::default()).expect("plan");
+        assert_eq!(plan.new_dir, dir.path().join("pdf-tools"));
+        let contents: Vec<&str> = plan
+            .edits
+            .iter()
+            .map(|edit| edit.content.as_str())
+            .collect();
+        assert_eq!(
+            contents,
+            [
+                "---\nname: pdf-tools\ndescription: Use when reading PDFs\n---\n\n# PDF\n\nSee [pdf](skill:pdf-tools).\n",
+                "---\nname: docx\ndescription: Use when writing\nmetadata:\n  related: \"pdf-tools, pdfx\"\n---\n\nUse [pdf](skill:pdf-tools#usage), not `skill:pdf`, or [x][ref].\n\n[ref]: skill:pdf-tools\n",
+            ]
+        );
+
+        apply_rename(&plan).expect("apply");
+        assert!(!dir.path().join("pdf").exists());
+        assert_eq!(
+            fs::read_to_string(dir.path().join("pdf-tools/SKILL.md")).expect("read"),
+            contents[0]
+        );
+    }
+
+    #[test]
+    fn refuses_invalid_renames() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skills = [
+            write(dir.path(), "pdf", "---\nname: pdf\ndescription: A\n---\n"),
+            write(dir.path(), "docx", "---\nname: docx\ndescription: B\n---\n"),
+        ];
+        fs::create_dir_all(dir.path().join("taken")).expect("mkdir");
+        let config = Config::default();
+
+        assert!(matches!(
+            plan_rename(&skills, "pdf", "PDF", &config),
+            Err(FixError::InvalidName { .. })
+        ));
+        assert!(matches!(
+            plan_rename(&skills, "ocr", "text", &config),
+            Err(FixError::UnknownSkill(name)) if name == "ocr"
+        ));
+        assert!(matches!(
+            plan_rename(&skills, "pdf", "docx", &config),
+            Err(FixError::SkillExists(name)) if name == "docx"
+        ));
+        assert!(matches!(
+            plan_rename(&skills, "pdf", "taken", &config),
+            Err(FixError::TargetExists(path)) if path == dir.path().join("taken")
+        ));
+    }
+
+    #[test]
+    fn reformats_multiline_frontmatter() {
+        let content = "---\nname: >-\n  pdf\ndescription: A\n---\nBody\n";
+        assert_eq!(
+            rewrite_skill(content, "pdf", "pdf-tools", true, &Config::default()).expect("rewrite"),
+            "---\nname: \"pdf-tools\"\ndescription: \"A\"\n---\nBody\n"
+        );
+    }
+}
@@ -0,0 +1,349 @@
+//! Checks for bundled scripts.
+//!
+//! Agents run the scripts a skill references directly, so a script without a
+//! shebang or the executable bit fails with "permission denied" or "exec
+//! format error", and a CRLF line ending turns `python3` into `python3\r`.
+//! [`check_scripts`] reports these problems for referenced files under
+//! `scripts/`, and [`fix_script_permissions`] sets the missing executable bits.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::error::{FixError, ValidationError};
+use crate::references::{referenced_files, relative_display};
+
+/// Extensions of files treated as scripts even without a shebang.
+const SCRIPT_EXTENSIONS: [&str; 19] = [
+    "sh", "bash", "zsh", "ksh", "fish", "py", "js", "mjs", "cjs", "ts", "mts", "rb", "pl", "php",
+    "lua", "r", "ps1", "bat", "cmd",
+];
+
+/// Windows scripts, which need neither a shebang nor the executable bit.
+const WINDOWS_EXTENSIONS: [&str; 3] = ["ps1", "bat", "cmd"];
+
+/// Report shebang, permission and line-ending problems in referenced scripts.
+///
+/// Only files under `scripts/` that the body references (directly, in a code
+/// block such as a shell example, through referenced Markdown, or through a
+/// referenced directory) are checked.
+/// Binary files are skipped.
+pub fn check_scripts(skill_dir: &Path, body: &str) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    for script in referenced_scripts(skill_dir, body) {
+        let path = script.display.clone();
+        let windows = script.is_windows();
+
+        if !windows && script.shebang.is_none() {
+            errors.push(ValidationError::ScriptMissingShebang(path.clone()));
+        }
+        if !windows && !is_executable(&script.path) {
+            errors.push(ValidationError::ScriptNotExecutable(path.clone()));
+        }
+
+        let crlf = script.content.matches("\r\n").count();
+        let lf = script.content.matches('\n').count() - crlf;
+        let endings = match (crlf, lf) {
+            (0, _) => None,
+            (_, 0) if windows => None,
+            (_, 0) => Some("CRLF"),
+            _ => Some("mixed"),
+        };
+        if let Some(endings) = endings {
+            errors.push(ValidationError::ScriptLineEndings {
+                path: path.clone(),
+                endings,
+            });
+        }
+
+        if let (Some(interpreter), Some(extension)) =
+            (script.interpreter(), script.extension.as_deref())
+        {
+            let matches = interpreter_extensions(&interpreter)
+                .is_none_or(|extensions| extensions.contains(&extension));
+            if !matches && SCRIPT_EXTENSIONS.contains(&extension) {
+                errors.push(ValidationError::ShebangMismatch {
+                    path,
+                    interpreter,
+                    extension: extension.to_string(),
+                });
+            }
+        }
+    }
+    errors
+}
+
+/// Set the executable bit on referenced scripts that lack it.
+///
+/// Returns the scripts that were (or, with `dry_run`, would be) changed,
+/// relative to the skill directory. Does nothing on platforms without Unix
+/// permissions.
+pub fn fix_script_permissions(
+    skill_dir: &Path,
+    body: &str,
+    dry_run: bool,
+) -> Result<Vec<String>, FixError> {
+    let mut changed = Vec::new();
+    for script in referenced_scripts(skill_dir, body) {
+        if script.is_windows() || is_executable(&script.path) {
+            continue;
+        }
+        if !dry_run {
+            set_executable(&script.path).map_err(|source| FixError::PermissionsFailed {
+                path: script.path.clone(),
+                source,
+            })?;
+        }
+        changed.push(script.display);
+    }
+    Ok(changed)
+}
+
+struct Script {
+    path: PathBuf,
+    display: String,
+    extension: Option<String>,
+    shebang: Option<String>,
+    content: String,
+}
+
+impl Script {
+    fn is_windows(&self) -> bool {
+        self.extension
+            .as_deref()
+            .is_some_and(|extension| WINDOWS_EXTENSIONS.contains(&extension))
+    }
+
+    /// The interpreter named by the shebang, without directory or version.
+    ///
+    /// `#!/usr/bin/env -S python3.12 -u` yields `python`.
+    fn interpreter(&self) -> Option<String> {
+        let mut words = self.shebang.as_deref()?.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = words.find(|word| !word.starts_with('-') && !word.contains('='))?;
+        }
+        let name = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        (!name.is_empty()).then(|| name.to_string())
+    }
+}
+
+fn referenced_scripts(skill_dir: &Path, body: &str) -> Vec<Script> {
+    let scripts_dir = skill_dir.join("scripts");
+    referenced_files(skill_dir, body)
+        .into_iter()
+        .filter(|path| path.starts_with(&scripts_dir))
+        .filter_map(|path| {
+            let bytes = fs::read(&path).ok()?;
+            if bytes.contains(&0) {
+                return None;
+            }
+            let content = String::from_utf8_lossy(&bytes).into_owned();
+            let shebang = content
+                .strip_prefix("#!")
+                .map(|rest| rest.lines().next().unwrap_or_default().trim().to_string());
+            let extension = path
+                .extension()
+                .and_then(|extension| extension.to_str())
+                .map(str::to_ascii_lowercase);
+            let is_script = shebang.is_some()
+                || extension
+                    .as_deref()
+                    .is_some_and(|extension| SCRIPT_EXTENSIONS.contains(&extension));
+            if !is_script {
+                return None;
+            }
+            let rel = path.strip_prefix(skill_dir).unwrap_or(&path);
+            Some(Script {
+                display: relative_display(rel),
+                path,
+                extension,
+                shebang,
+                content,
+            })
+        })
+        .collect()
+}
+
+/// File extensions that suit scripts run by `interpreter`, if it is known.
+fn interpreter_extensions(interpreter: &str) -> Option<&'static [&'static str]> {
+    Some(match interpreter {
+        "sh" | "bash" | "zsh" | "dash" | "ksh" => &["sh", "bash", "zsh", "ksh"],
+        "fish" => &["fish"],
+        "python" | "pypy" | "uv" => &["py"],
+        "node" | "nodejs" | "deno" | "bun" | "tsx" | "ts-node" => {
+            &["js", "mjs", "cjs", "ts", "mts"]
+        }
+        "ruby" => &["rb"],
+        "perl" => &["pl"],
+        "php" => &["php"],
+        "lua" => &["lua"],
+        "Rscript" => &["r"],
+        "pwsh" => &["ps1"],
+        _ => return None,
+    })
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+
+    fs::metadata(path).is_ok_and(|metadata| metadata.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(_path: &Path) -> bool {
+    true
+}
+
+#[cfg(unix)]
+fn set_executable(path: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut permissions = fs::metadata(path)?.permissions();
+    // Grant execute wherever read is granted, as `chmod +x` does under a 022 umask.
+    let mode = permissions.mode();
+    permissions.set_mode(mode | ((mode & 0o444) >> 2));
+    fs::set_permissions(path, permissions)
+}
+
+#[cfg(not(unix))]
+fn set_executable(_path: &Path) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_script(dir: &Path, name: &str, content: &str, executable: bool) {
+        let path = dir.join("scripts").join(name);
+        fs::create_dir_all(path.parent().expect("parent")).expect("mkdir");
+        fs::write(&path, content).expect("write");
+        if executable {
+            set_executable(&path).expect("chmod");
+        }
+    }
+
+    fn codes(dir: &Path, body: &str) -> Vec<&'static str> {
+        check_scripts(dir, body)
+            .iter()
+            .map(ValidationError::code)
+            .collect()
+    }
+
+    #[test]
+    fn accepts_well_formed_scripts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_script(
+            dir.path(),
+            "run.py",
+            "#!/usr/bin/env python3\nprint(1)\n",
+            true,
+        );
+        write_script(dir.path(), "build.sh", "#!/bin/bash\necho ok\n", true);
+        write_script(dir.path(), "setup.ps1", "Write-Host ok\r\n", false);
+        write_script(dir.path(), "data.json", "{}\n", false);
+        write_script(dir.path(), "unreferenced.py", "print(1)\n", false);
+
+        assert!(codes(
+            dir.path(),
+            "Run `scripts/run.py`, `scripts/build.sh`, `scripts/setup.ps1` and `scripts/data.json`."
+        )
+        .is_empty());
+    }
+
+    #[test]
+    fn reports_script_problems() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_script(dir.path(), "a.py", "print(1)\n", true);
+        write_script(dir.path(), "b.sh", "#!/bin/sh\r\necho ok\r\n", true);
+        write_script(
+            dir.path(),
+            "c.py",
+            "#!/usr/bin/env node\nconsole.log(1)\n",
+            true,
+        );
+        write_script(dir.path(), "d", "#!/bin/sh\necho ok\r\n", false);
+
+        let errors = check_scripts(dir.path(), "See [scripts](scripts/).");
+        #[cfg(unix)]
+        let not_executable = vec![ValidationError::ScriptNotExecutable(
+            "scripts/d".to_string(),
+        )];
+        #[cfg(not(unix))]
+        let not_executable = Vec::new();
+        let mut expected = vec![
+            ValidationError::ScriptMissingShebang("scripts/a.py".to_string()),
+            ValidationError::ScriptLineEndings {
+                path: "scripts/b.sh".to_string(),
+                endings: "CRLF",
+            },
+            ValidationError::ShebangMismatch {
+                path: "scripts/c.py".to_string(),
+                interpreter: "node".to_string(),
+                extension: "py".to_string(),
+            },
+        ];
+        expected.extend(not_executable);
+        expected.push(ValidationError::ScriptLineEndings {
+            path: "scripts/d".to_string(),
+            endings: "mixed",
+        });
+        assert_eq!(errors, expected);
+    }
+
+    #[test]
+    fn checks_scripts_invoked_in_code_blocks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_script(dir.path(), "run.py", "print(1)\n", true);
+
+        assert_eq!(
+            codes(
+                dir.path(),
+                "Run it:\n\n```bash\npython3 scripts/run.py\n```\n"
+            ),
+            ["script-missing-shebang"]
+        );
+    }
+
+    #[test]
+    fn reads_interpreter_from_env_shebang() {
+        let script = |shebang: &str| Script {
+            path: PathBuf::new(),
+            display: String::new(),
+            extension: None,
+            shebang: Some(shebang.to_string()),
+            content: String::new(),
+        };
+        assert_eq!(
+            script("/usr/bin/env -S python3.12 -u").interpreter(),
+            Some("python".to_string())
+        );
+        assert_eq!(
+            script("/usr/bin/env PATH=/bin bash").interpreter(),
+            Some("bash".to_string())
+        );
+        assert_eq!(script("/bin/sh -e").interpreter(), Some("sh".to_string()));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn fix_sets_executable_bit() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        write_script(dir.path(), "run.sh", "#!/bin/sh\necho ok\n", false);
+        let body = "Run `scripts/run.sh`.";
+
+        assert_eq!(
+            fix_script_permissions(dir.path(), body, true).expect("dry run"),
+            ["scripts/run.sh"]
+        );
+        assert!(!is_executable(&dir.path().join("scripts/run.sh")));
+
+        assert_eq!(
+            fix_script_permissions(dir.path(), body, false).expect("fix"),
+            ["scripts/run.sh"]
+        );
+        assert!(is_executable(&dir.path().join("scripts/run.sh")));
+        assert!(codes(dir.path(), body).is_empty());
+    }
+}
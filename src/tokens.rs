@@ -0,0 +1,158 @@
+//! Offline token estimation.
+//!
+//! Context budgets are spent in tokens, not characters, and the ratio between
+//! the two varies a lot: English prose is about four characters per token,
+//! while code, numbers and non-Latin scripts are much denser.
+//! [`estimate_tokens`] approximates a BPE tokenizer with a few character-class
+//! rules, so no vocabulary has to be bundled.
+
+/// Characters of a Latin word that fit in one token.
+const CHARS_PER_WORD_TOKEN: usize = 4;
+/// Words up to this length are usually a single token.
+const SINGLE_TOKEN_WORD_LEN: usize = 6;
+/// Digits that fit in one token.
+const DIGITS_PER_TOKEN: usize = 3;
+/// Repeated punctuation characters (`----`, `====`) that fit in one token.
+const REPEATS_PER_TOKEN: usize = 8;
+
+/// Estimate the number of tokens a language model would see for `text`.
+///
+/// The estimate is deterministic and tends to slightly overcount, so it is safe
+/// to compare against a budget:
+///
+/// - a Latin word of up to six letters is one token, longer words add one token
+///   per four further letters; a single leading space is free;
+/// - digits are grouped three to a token;
+/// - each punctuation character is a token, except runs of the same character;
+/// - a line break or a run of other whitespace is one token;
+/// - CJK characters are one token each, other non-ASCII letters two per token.
+///
+/// ```
+/// use agent_skills_lint::estimate_tokens;
+///
+/// assert_eq!(estimate_tokens(""), 0);
+/// assert_eq!(estimate_tokens("Use this skill"), 3);
+/// ```
+pub fn estimate_tokens(text: &str) -> usize {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens = 0;
+    let mut i = 0;
+
+    while i < chars.len() {
+        let c = chars[i];
+        let kind = class(c);
+        let run = if kind == CharClass::Punct {
+            run_length(&chars, i, |other| other == c)
+        } else {
+            run_length(&chars, i, |other| class(other) == kind)
+        };
+        tokens += match kind {
+            CharClass::Latin => word_tokens(run),
+            CharClass::Digit => run.div_ceil(DIGITS_PER_TOKEN),
+            CharClass::Wide => run,
+            CharClass::OtherLetter => run.div_ceil(2),
+            // A single space before a word is part of that word's token.
+            CharClass::Space => {
+                usize::from(run > 1 || chars.get(i + 1).is_none_or(|&next| !is_wordish(next)))
+            }
+            CharClass::Newline | CharClass::OtherSpace => 1,
+            CharClass::Punct => run.div_ceil(REPEATS_PER_TOKEN),
+        };
+        i += run;
+    }
+
+    tokens
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum CharClass {
+    Latin,
+    Digit,
+    Wide,
+    OtherLetter,
+    Space,
+    Newline,
+    OtherSpace,
+    Punct,
+}
+
+fn class(c: char) -> CharClass {
+    match c {
+        'a'..='z' | 'A'..='Z' => CharClass::Latin,
+        '0'..='9' => CharClass::Digit,
+        ' ' => CharClass::Space,
+        '\n' | '\r' => CharClass::Newline,
+        c if c.is_whitespace() => CharClass::OtherSpace,
+        c if is_wide(c) => CharClass::Wide,
+        c if c.is_alphabetic() => CharClass::OtherLetter,
+        _ => CharClass::Punct,
+    }
+}
+
+fn is_wordish(c: char) -> bool {
+    matches!(
+        class(c),
+        CharClass::Latin | CharClass::Digit | CharClass::OtherLetter
+    )
+}
+
+/// CJK ideographs, kana, hangul and emoji, which tokenize at about one per character.
+fn is_wide(c: char) -> bool {
+    matches!(
+        u32::from(c),
+        0x1100..=0x11FF
+            | 0x2E80..=0x9FFF
+            | 0xAC00..=0xD7AF
+            | 0xF900..=0xFAFF
+            | 0xFF00..=0xFFEF
+            | 0x1F300..=0x1FAFF
+            | 0x20000..=0x3FFFF
+    )
+}
+
+const fn word_tokens(len: usize) -> usize {
+    if len <= SINGLE_TOKEN_WORD_LEN {
+        1
+    } else {
+        1 + (len - SINGLE_TOKEN_WORD_LEN).div_ceil(CHARS_PER_WORD_TOKEN)
+    }
+}
+
+fn run_length(chars: &[char], start: usize, same: impl Fn(char) -> bool) -> usize {
+    chars[start..].iter().take_while(|&&c| same(c)).count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_words_and_spaces() {
+        assert_eq!(estimate_tokens("hello"), 1);
+        assert_eq!(estimate_tokens("hello world"), 2);
+        assert_eq!(estimate_tokens("internationalization"), 5);
+        assert_eq!(estimate_tokens("a  b"), 3);
+        assert_eq!(estimate_tokens("line\nline"), 3);
+    }
+
+    #[test]
+    fn counts_digits_punctuation_and_wide_characters() {
+        assert_eq!(estimate_tokens("1234567"), 3);
+        assert_eq!(estimate_tokens("a, b."), 4);
+        assert_eq!(estimate_tokens("----------------"), 2);
+        assert_eq!(estimate_tokens("日本語"), 3);
+        assert_eq!(estimate_tokens("привет"), 3);
+    }
+
+    #[test]
+    fn english_prose_is_close_to_four_characters_per_token() {
+        let text = "Extract text and tables from PDF files, fill forms, and merge \
+                    documents. Use when the user mentions PDFs, forms, or document extraction.";
+        let chars = text.chars().count();
+        let tokens = estimate_tokens(text);
+        assert!(
+            (chars / 6..=chars / 3).contains(&tokens),
+            "{tokens} tokens for {chars} chars"
+        );
+    }
+}
@@ -0,0 +1,163 @@
+//! Detection of hidden and deceptive Unicode.
+//!
+//! Invisible code points let a skill carry instructions a reviewer cannot see
+//! (bidirectional overrides, zero-width characters, tag characters), and
+//! homoglyphs let a skill impersonate a trusted one (`pаypal` with a Cyrillic
+//! `а`). Skill names accept any alphanumeric character after NFKC, so these
+//! checks are separate from the naming rules in [`crate::validation`].
+
+use unicode_security::confusable_detection::skeleton;
+use unicode_security::MixedScript;
+
+use crate::error::ValidationError;
+
+/// Report invisible or direction-changing code points in SKILL.md content.
+///
+/// Lines are numbered from 1. A leading byte order mark is ignored, as are a
+/// zero-width joiner inside an emoji sequence and a zero-width non-joiner
+/// between non-Latin letters, where they are required for correct rendering.
+/// Each code point is reported once per line.
+pub fn find_hidden_characters(content: &str) -> Vec<ValidationError> {
+    let content = content.strip_prefix('\u{feff}').unwrap_or(content);
+    let mut errors = Vec::new();
+
+    for (index, line) in content.lines().enumerate() {
+        let chars: Vec<char> = line.chars().collect();
+        let mut seen: Vec<char> = Vec::new();
+        for (position, &c) in chars.iter().enumerate() {
+            let Some(kind) = hidden_kind(c) else {
+                continue;
+            };
+            let previous = position.checked_sub(1).map(|i| chars[i]);
+            let next = chars.get(position + 1).copied();
+            if is_required_joiner(c, previous, next) || seen.contains(&c) {
+                continue;
+            }
+            seen.push(c);
+            errors.push(ValidationError::HiddenCharacter {
+                line: index + 1,
+                code_point: format!("U+{:04X}", u32::from(c)),
+                kind,
+            });
+        }
+    }
+
+    errors
+}
+
+/// Report a skill name that mixes scripts or imitates an ASCII name.
+pub fn check_name_confusables(name: &str) -> Option<ValidationError> {
+    let name = name.trim();
+    if name.is_ascii() {
+        return None;
+    }
+    if !name.is_single_script() {
+        return Some(ValidationError::MixedScriptName(name.to_string()));
+    }
+    let lookalike: String = skeleton(name).collect();
+    if lookalike.is_ascii() && lookalike != name {
+        return Some(ValidationError::ConfusableName {
+            name: name.to_string(),
+            lookalike,
+        });
+    }
+    None
+}
+
+fn hidden_kind(c: char) -> Option<&'static str> {
+    match u32::from(c) {
+        0x061C | 0x200E | 0x200F | 0x202A..=0x202E | 0x2066..=0x2069 => {
+            Some("bidirectional control")
+        }
+        0x200B..=0x200D | 0x2060 | 0xFEFF | 0x180E => Some("zero-width character"),
+        0xE0000..=0xE007F => Some("tag character"),
+        0xE0100..=0xE01EF => Some("variation selector"),
+        0x00AD | 0x034F | 0x115F | 0x1160 | 0x17B4 | 0x17B5 | 0x2061..=0x2064 | 0x3164 | 0xFFA0 => {
+            Some("invisible character")
+        }
+        0x0000..=0x0008 | 0x000B | 0x000C | 0x000E..=0x001F | 0x007F..=0x009F => {
+            Some("control character")
+        }
+        _ => None,
+    }
+}
+
+fn is_required_joiner(c: char, previous: Option<char>, next: Option<char>) -> bool {
+    match c {
+        // Emoji ZWJ sequences such as family or profession emoji.
+        '\u{200D}' => previous.is_some_and(is_pictographic) && next.is_some_and(is_pictographic),
+        // ZWNJ controls ligatures in Persian and Indic scripts.
+        '\u{200C}' => {
+            previous.is_some_and(|p| p.is_alphabetic() && !p.is_ascii())
+                && next.is_some_and(|n| n.is_alphabetic() && !n.is_ascii())
+        }
+        _ => false,
+    }
+}
+
+fn is_pictographic(c: char) -> bool {
+    matches!(u32::from(c), 0x2600..=0x27BF | 0xFE0F | 0x1F000..=0x1FAFF)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_hidden_characters_by_line() {
+        let content =
+            "---\nname: a\u{200B}b\n---\nRun \u{202E}txt.exe\u{202C} now\u{E0041}\u{E0041}\n";
+        assert_eq!(
+            find_hidden_characters(content),
+            [
+                ValidationError::HiddenCharacter {
+                    line: 2,
+                    code_point: "U+200B".to_string(),
+                    kind: "zero-width character",
+                },
+                ValidationError::HiddenCharacter {
+                    line: 4,
+                    code_point: "U+202E".to_string(),
+                    kind: "bidirectional control",
+                },
+                ValidationError::HiddenCharacter {
+                    line: 4,
+                    code_point: "U+202C".to_string(),
+                    kind: "bidirectional control",
+                },
+                ValidationError::HiddenCharacter {
+                    line: 4,
+                    code_point: "U+E0041".to_string(),
+                    kind: "tag character",
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_bom_and_required_joiners() {
+        let content = "\u{feff}---\nname: a\n---\nFamily 👨\u{200D}👩 and می\u{200C}خواهم\tok\n";
+        assert!(find_hidden_characters(content).is_empty());
+        assert_eq!(find_hidden_characters("a\u{200D}b").len(), 1);
+    }
+
+    #[test]
+    fn detects_confusable_names() {
+        assert_eq!(check_name_confusables("pdf-tools"), None);
+        assert_eq!(check_name_confusables("日本語"), None);
+        assert_eq!(check_name_confusables("données"), None);
+        assert_eq!(
+            check_name_confusables("p\u{0430}ypal"),
+            Some(ValidationError::MixedScriptName(
+                "p\u{0430}ypal".to_string()
+            ))
+        );
+        assert_eq!(
+            check_name_confusables("\u{0440}\u{0430}\u{0443}"),
+            Some(ValidationError::ConfusableName {
+                name: "\u{0440}\u{0430}\u{0443}".to_string(),
+                lookalike: "pay".to_string(),
+            })
+        );
+    }
+}
@@ -8,10 +8,18 @@
 //! - `name`: Required, lowercase, 1-64 chars, alphanumeric + hyphen only,
 //!   no leading/trailing/consecutive hyphens, must match directory name (NFKC normalized)
 //! - `description`: Required, 1-1024 chars
-//! - `license`: Optional string, must be non-empty if provided
+//! - `license`: Optional, a valid SPDX license expression or a reference to a
+//!   license file that exists in the skill directory (e.g. "See LICENSE.txt")
 //! - `compatibility`: Optional, max 500 chars
 //! - `allowed-tools`: Optional, space-delimited string (experimental)
-//! - `metadata`: Optional key-value pairs
+//! - `metadata`: Optional key-value pairs; config may require keys and
+//!   constrain their values. `metadata.version`, if present, must be a
+//!   semantic version
+//!
+//! Other [profiles](crate::profile::Profile) may allow additional
+//! runtime-specific fields; the limits are the same for every profile.
+//! Additional fields declared in the [`Config`] are validated against their
+//! configured type, required flag, and length limit.
 
 use std::collections::BTreeMap;
 use std::path::Path;
@@ -19,10 +27,10 @@ use std::path::Path;
 use serde_yaml::Value;
 use unicode_normalization::UnicodeNormalization;
 
+use crate::config::{Config, FieldKind, FieldSpec};
 use crate::error::ValidationError;
-use crate::skill::{
-    ALLOWED_FIELDS, MAX_COMPATIBILITY_LENGTH, MAX_DESCRIPTION_LENGTH, MAX_SKILL_NAME_LENGTH,
-};
+use crate::references::resolve_reference;
+use crate::skill::{MAX_COMPATIBILITY_LENGTH, MAX_DESCRIPTION_LENGTH, MAX_SKILL_NAME_LENGTH};
 
 /// Validate the metadata extracted from a SKILL.md file.
 ///
@@ -35,18 +43,33 @@ use crate::skill::{
 pub fn validate_metadata(
     metadata: &BTreeMap<String, Value>,
     skill_dir: Option<&Path>,
+) -> Vec<ValidationError> {
+    validate_metadata_with_config(metadata, skill_dir, &Config::default())
+}
+
+/// Validate metadata against the profile and custom fields in `config`.
+pub fn validate_metadata_with_config(
+    metadata: &BTreeMap<String, Value>,
+    skill_dir: Option<&Path>,
+    config: &Config,
 ) -> Vec<ValidationError> {
     let mut errors = Vec::new();
+    let profile = config.profile;
 
     let extra_fields: Vec<String> = metadata
         .keys()
-        .filter(|key| !ALLOWED_FIELDS.contains(&key.as_str()))
+        .filter(|key| !config.allows(key))
         .cloned()
         .collect();
     if !extra_fields.is_empty() {
         errors.push(ValidationError::UnexpectedFields {
             fields: extra_fields.join(", "),
-            allowed: &ALLOWED_FIELDS,
+            allowed: profile
+                .allowed_fields()
+                .iter()
+                .map(ToString::to_string)
+                .chain(config.fields.iter().map(|spec| spec.name.clone()))
+                .collect(),
         });
     }
 
@@ -67,7 +90,7 @@ pub fn validate_metadata(
     }
 
     if let Some(value) = metadata.get("license") {
-        errors.extend(validate_license(value));
+        errors.extend(validate_license(value, skill_dir));
     }
 
     if let Some(value) = metadata.get("compatibility") {
@@ -77,18 +100,33 @@ pub fn validate_metadata(
         }
     }
 
-    if let Some(value) = metadata.get("metadata") {
-        errors.extend(validate_metadata_field(value));
+    match metadata.get("metadata") {
+        Some(value) => errors.extend(validate_metadata_field(value, &config.metadata_keys)),
+        None => errors.extend(
+            config
+                .metadata_keys
+                .iter()
+                .filter(|spec| spec.required)
+                .map(|spec| ValidationError::MissingMetadataKey(spec.name.clone())),
+        ),
     }
 
     if let Some(value) = metadata.get("allowed-tools") {
         errors.extend(validate_allowed_tools(value));
     }
 
+    for spec in profile.field_specs().iter().chain(&config.fields) {
+        match metadata.get(&spec.name) {
+            Some(value) => errors.extend(validate_custom_field(spec, value)),
+            None if spec.required => errors.push(ValidationError::MissingField(spec.name.clone())),
+            None => {}
+        }
+    }
+
     errors
 }
 
-fn validate_name(name: &str, skill_dir: Option<&Path>) -> Vec<ValidationError> {
+pub(crate) fn validate_name(name: &str, skill_dir: Option<&Path>) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
     if name.trim().is_empty() {
@@ -176,7 +214,7 @@ fn validate_compatibility(compatibility: &str) -> Vec<ValidationError> {
     errors
 }
 
-fn validate_metadata_field(value: &Value) -> Vec<ValidationError> {
+fn validate_metadata_field(value: &Value, policy: &[FieldSpec]) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
     let Value::Mapping(map) = value else {
@@ -198,16 +236,66 @@ fn validate_metadata_field(value: &Value) -> Vec<ValidationError> {
         }
     }
 
+    if let Some(Value::String(version)) = map.get("version") {
+        if let Err(err) = semver::Version::parse(version) {
+            errors.push(ValidationError::InvalidVersion {
+                version: version.clone(),
+                reason: err.to_string(),
+            });
+        }
+    }
+
+    for spec in policy {
+        match map.get(spec.name.as_str()) {
+            // Non-string values are already reported above.
+            Some(value @ Value::String(_)) => {
+                let qualified = FieldSpec {
+                    name: format!("metadata.{}", spec.name),
+                    ..spec.clone()
+                };
+                errors.extend(validate_custom_field(&qualified, value));
+            }
+            None if spec.required => {
+                errors.push(ValidationError::MissingMetadataKey(spec.name.clone()));
+            }
+            _ => {}
+        }
+    }
+
     errors
 }
 
-fn validate_license(value: &Value) -> Vec<ValidationError> {
+fn validate_license(value: &Value, skill_dir: Option<&Path>) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
     match value {
         Value::String(license) => {
-            if license.trim().is_empty() {
+            let license = license.trim();
+            if license.is_empty() {
                 errors.push(ValidationError::EmptyLicense);
+                return errors;
+            }
+
+            let Err(err) = spdx::Expression::parse(license) else {
+                return errors;
+            };
+
+            if let Some(file) = license_file_reference(license) {
+                // Without a skill directory only containment can be checked.
+                match resolve_reference(skill_dir.unwrap_or_else(|| Path::new("")), file) {
+                    Err(_) => {
+                        errors.push(ValidationError::LicenseFileOutsideSkill(file.to_string()));
+                    }
+                    Ok(path) if skill_dir.is_some() && !path.is_file() => {
+                        errors.push(ValidationError::LicenseFileNotFound(file.to_string()));
+                    }
+                    Ok(_) => {}
+                }
+            } else {
+                errors.push(ValidationError::InvalidLicense {
+                    license: license.to_string(),
+                    reason: err.reason.to_string(),
+                });
             }
         }
         _ => {
@@ -218,6 +306,40 @@ fn validate_license(value: &Value) -> Vec<ValidationError> {
     errors
 }
 
+/// Find a license file named in free-form license text.
+///
+/// Accepts the explicit "See <file>" form, such as "See terms.md", and
+/// conventional license file names anywhere in the text, such as
+/// "Complete terms in docs/LICENSE".
+fn license_file_reference(license: &str) -> Option<&str> {
+    let mut words = license.split_whitespace().map(|word| {
+        word.trim_start_matches(['"', '\'', '(', '`'])
+            .trim_end_matches(['"', '\'', ')', '`', '.', ',', ';', ':'])
+    });
+    let first = words.clone().next()?;
+    if first.eq_ignore_ascii_case("see") {
+        return words.nth(1).filter(|word| is_path_like(word));
+    }
+    words.find(|word| is_path_like(word) && is_license_file_name(word))
+}
+
+fn is_path_like(word: &str) -> bool {
+    !word.is_empty()
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || matches!(c, '.' | '_' | '-' | '/' | '\\' | '~' | ':'))
+}
+
+fn is_license_file_name(word: &str) -> bool {
+    let file_name = word.rsplit(['/', '\\']).next().unwrap_or(word);
+    // Conventional license files are upper case; "License" as a word is prose.
+    ["LICENSE", "LICENCE", "COPYING"].iter().any(|name| {
+        file_name
+            .strip_prefix(name)
+            .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', '-', '_']))
+    })
+}
+
 fn validate_allowed_tools(value: &Value) -> Vec<ValidationError> {
     let mut errors = Vec::new();
 
@@ -258,6 +380,73 @@ fn validate_allowed_tools(value: &Value) -> Vec<ValidationError> {
     errors
 }
 
+fn validate_custom_field(spec: &FieldSpec, value: &Value) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+    let invalid_type = || ValidationError::InvalidFieldType {
+        field: spec.name.clone(),
+        expected: spec.kind.expected(),
+    };
+
+    match (&spec.kind, value) {
+        (FieldKind::Bool, Value::Bool(_)) => {}
+        (FieldKind::String { pattern }, Value::String(text)) => {
+            errors.extend(validate_custom_string(spec, text));
+            if let Some(pattern) = pattern {
+                if !pattern.is_match(text) {
+                    errors.push(ValidationError::FieldPatternMismatch {
+                        field: spec.name.clone(),
+                        value: text.clone(),
+                        pattern: pattern.as_str().to_string(),
+                    });
+                }
+            }
+        }
+        (FieldKind::Enum(allowed), Value::String(text)) => {
+            if !allowed.contains(text) {
+                errors.push(ValidationError::FieldValueNotAllowed {
+                    field: spec.name.clone(),
+                    value: text.clone(),
+                    allowed: allowed.clone(),
+                });
+            }
+        }
+        (FieldKind::List, Value::Sequence(items)) => {
+            for item in items {
+                let Value::String(text) = item else {
+                    errors.push(invalid_type());
+                    break;
+                };
+                errors.extend(validate_custom_string(spec, text));
+            }
+        }
+        _ => errors.push(invalid_type()),
+    }
+
+    errors
+}
+
+fn validate_custom_string(spec: &FieldSpec, text: &str) -> Vec<ValidationError> {
+    let mut errors = Vec::new();
+
+    if text.trim().is_empty() {
+        errors.push(ValidationError::EmptyField(spec.name.clone()));
+        return errors;
+    }
+
+    let char_count = text.chars().count();
+    if let Some(limit) = spec.max_length {
+        if char_count > limit {
+            errors.push(ValidationError::FieldTooLong {
+                field: spec.name.clone(),
+                limit,
+                actual: char_count,
+            });
+        }
+    }
+
+    errors
+}
+
 fn validate_tool_spec(tool: &str) -> Option<String> {
     let open_parens = tool.chars().filter(|&c| c == '(').count();
     let close_parens = tool.chars().filter(|&c| c == ')').count();
@@ -281,6 +470,7 @@ fn validate_tool_spec(tool: &str) -> Option<String> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::profile::Profile;
 
     fn base_metadata() -> BTreeMap<String, Value> {
         let mut metadata = BTreeMap::new();
@@ -320,6 +510,296 @@ mod tests {
             .any(|e| matches!(e, ValidationError::InvalidType(f) if f == "license")));
     }
 
+    #[test]
+    fn test_validate_license_spdx_expressions() {
+        for license in [
+            "Apache-2.0",
+            "MIT OR Apache-2.0",
+            "(MIT AND BSD-3-Clause) OR GPL-3.0-or-later",
+            "Apache-2.0 WITH LLVM-exception",
+            "LicenseRef-Proprietary",
+        ] {
+            let mut metadata = base_metadata();
+            metadata.insert("license".to_string(), Value::String(license.to_string()));
+            let errors = validate_metadata(&metadata, None);
+            assert!(errors.is_empty(), "{license}: {errors:?}");
+        }
+    }
+
+    #[test]
+    fn test_validate_license_invalid_expression() {
+        for license in ["Not A License", "MIT OR", "Apache 2"] {
+            let mut metadata = base_metadata();
+            metadata.insert("license".to_string(), Value::String(license.to_string()));
+            let errors = validate_metadata(&metadata, None);
+            assert!(
+                errors
+                    .iter()
+                    .any(|e| matches!(e, ValidationError::InvalidLicense { .. })),
+                "{license}: {errors:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_validate_license_file_reference() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let skill_dir = dir.path().join("my-skill");
+        std::fs::create_dir_all(&skill_dir).expect("mkdir");
+
+        let mut metadata = base_metadata();
+        metadata.insert(
+            "license".to_string(),
+            Value::String("Complete terms in LICENSE.txt".to_string()),
+        );
+        let errors = validate_metadata(&metadata, Some(&skill_dir));
+        assert!(errors.iter().any(
+            |e| matches!(e, ValidationError::LicenseFileNotFound(file) if file == "LICENSE.txt")
+        ));
+
+        std::fs::write(skill_dir.join("LICENSE.txt"), "terms").expect("write license");
+        let errors = validate_metadata(&metadata, Some(&skill_dir));
+        assert!(errors.is_empty(), "{errors:?}");
+
+        std::fs::write(skill_dir.join("terms.md"), "terms").expect("write terms");
+        metadata.insert(
+            "license".to_string(),
+            Value::String("See terms.md".to_string()),
+        );
+        let errors = validate_metadata(&metadata, Some(&skill_dir));
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_validate_license_file_outside_skill() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let skill_dir = dir.path().join("my-skill");
+        std::fs::create_dir_all(&skill_dir).expect("mkdir");
+        std::fs::write(dir.path().join("LICENSE"), "terms").expect("write license");
+
+        for (license, file) in [
+            ("See ../LICENSE", "../LICENSE"),
+            ("See /etc/hosts", "/etc/hosts"),
+            ("Terms in docs/../../LICENSE", "docs/../../LICENSE"),
+        ] {
+            let mut metadata = base_metadata();
+            metadata.insert("license".to_string(), Value::String(license.to_string()));
+            for skill_dir in [Some(skill_dir.as_path()), None] {
+                let errors = validate_metadata(&metadata, skill_dir);
+                assert_eq!(
+                    errors,
+                    [ValidationError::LicenseFileOutsideSkill(file.to_string())],
+                    "{license}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_validate_license_prose_is_not_a_file() {
+        let dir = tempfile::TempDir::new().expect("temp dir");
+        let skill_dir = dir.path().join("my-skill");
+        std::fs::create_dir_all(&skill_dir).expect("mkdir");
+        for license in ["Apache 2.0", "Released under v1.2 terms"] {
+            let mut metadata = base_metadata();
+            metadata.insert("license".to_string(), Value::String(license.to_string()));
+            for skill_dir in [Some(skill_dir.as_path()), None] {
+                let errors = validate_metadata(&metadata, skill_dir);
+                assert!(
+                    matches!(errors.as_slice(), [ValidationError::InvalidLicense { .. }]),
+                    "{license}: {errors:?}"
+                );
+            }
+        }
+    }
+
+    fn custom_config() -> Config {
+        Config::parse(
+            r#"
+fields:
+  - name: owner
+    type: string
+    required: true
+    max-length: 8
+  - name: ticket
+    type: string
+    pattern: "^[A-Z]+-[0-9]+$"
+  - name: tier
+    type: enum
+    values: [gold, silver]
+  - name: internal
+    type: bool
+  - name: tags
+    type: list
+"#,
+        )
+        .expect("config")
+    }
+
+    #[test]
+    fn test_validate_custom_fields_valid() {
+        let config = custom_config();
+        let mut metadata = base_metadata();
+        metadata.insert("owner".to_string(), Value::String("team-a".to_string()));
+        metadata.insert("ticket".to_string(), Value::String("ABC-12".to_string()));
+        metadata.insert("tier".to_string(), Value::String("gold".to_string()));
+        metadata.insert("internal".to_string(), Value::Bool(true));
+        metadata.insert(
+            "tags".to_string(),
+            Value::Sequence(vec![Value::String("x".to_string())]),
+        );
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_validate_custom_fields_invalid() {
+        let config = custom_config();
+        let errors = validate_metadata_with_config(&base_metadata(), None, &config);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::MissingField(f) if f == "owner")));
+
+        let mut metadata = base_metadata();
+        metadata.insert(
+            "owner".to_string(),
+            Value::String("a-very-long-owner".to_string()),
+        );
+        metadata.insert("ticket".to_string(), Value::String("abc".to_string()));
+        metadata.insert("tier".to_string(), Value::String("platinum".to_string()));
+        metadata.insert("internal".to_string(), Value::String("yes".to_string()));
+        metadata.insert(
+            "tags".to_string(),
+            Value::Sequence(vec![Value::Number(1.into())]),
+        );
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::FieldTooLong { field, .. } if field == "owner")));
+        assert!(errors.iter().any(
+            |e| matches!(e, ValidationError::FieldPatternMismatch { field, .. } if field == "ticket")
+        ));
+        assert!(errors.iter().any(
+            |e| matches!(e, ValidationError::FieldValueNotAllowed { field, .. } if field == "tier")
+        ));
+        assert!(errors.iter().any(
+            |e| matches!(e, ValidationError::InvalidFieldType { field, .. } if field == "internal")
+        ));
+        assert!(errors.iter().any(
+            |e| matches!(e, ValidationError::InvalidFieldType { field, .. } if field == "tags")
+        ));
+        assert!(!errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::UnexpectedFields { .. })));
+    }
+
+    #[test]
+    fn test_validate_claude_code_profile() {
+        let mut metadata = base_metadata();
+        metadata.insert("model".to_string(), Value::String("sonnet".to_string()));
+        metadata.insert("disable-model-invocation".to_string(), Value::Bool(true));
+        metadata.insert(
+            "argument-hint".to_string(),
+            Value::String("[file]".to_string()),
+        );
+
+        let errors = validate_metadata(&metadata, None);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::UnexpectedFields { .. })));
+
+        let config = Config {
+            profile: Profile::ClaudeCode,
+            ..Config::default()
+        };
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors.is_empty(), "{errors:?}");
+
+        metadata.insert(
+            "user-invocable".to_string(),
+            Value::String("no".to_string()),
+        );
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ValidationError::InvalidFieldType { field, .. } if field == "user-invocable"
+        )));
+    }
+
+    #[test]
+    fn test_validate_metadata_key_policy() {
+        let config = Config::parse(
+            r#"
+metadata-keys:
+  - name: owner
+    required: true
+    pattern: "^@[a-z-]+$"
+  - name: team
+    values: [platform, search]
+"#,
+        )
+        .expect("config");
+
+        let errors = validate_metadata_with_config(&base_metadata(), None, &config);
+        assert!(errors
+            .iter()
+            .any(|e| matches!(e, ValidationError::MissingMetadataKey(k) if k == "owner")));
+
+        let mut map = serde_yaml::Mapping::new();
+        map.insert(
+            Value::String("owner".to_string()),
+            Value::String("someone".to_string()),
+        );
+        map.insert(
+            Value::String("team".to_string()),
+            Value::String("billing".to_string()),
+        );
+        let mut metadata = base_metadata();
+        metadata.insert("metadata".to_string(), Value::Mapping(map));
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ValidationError::FieldPatternMismatch { field, .. } if field == "metadata.owner"
+        )));
+        assert!(errors.iter().any(|e| matches!(
+            e,
+            ValidationError::FieldValueNotAllowed { field, .. } if field == "metadata.team"
+        )));
+
+        let mut map = serde_yaml::Mapping::new();
+        map.insert(
+            Value::String("owner".to_string()),
+            Value::String("@platform".to_string()),
+        );
+        let mut metadata = base_metadata();
+        metadata.insert("metadata".to_string(), Value::Mapping(map));
+        let errors = validate_metadata_with_config(&metadata, None, &config);
+        assert!(errors.is_empty(), "{errors:?}");
+    }
+
+    #[test]
+    fn test_validate_metadata_version_semver() {
+        for (version, valid) in [
+            ("1.2.3", true),
+            ("0.1.0-beta.1+build.5", true),
+            ("1.2", false),
+            ("v1.2.3", false),
+        ] {
+            let mut map = serde_yaml::Mapping::new();
+            map.insert(
+                Value::String("version".to_string()),
+                Value::String(version.to_string()),
+            );
+            let mut metadata = base_metadata();
+            metadata.insert("metadata".to_string(), Value::Mapping(map));
+            let errors = validate_metadata(&metadata, None);
+            let invalid = errors
+                .iter()
+                .any(|e| matches!(e, ValidationError::InvalidVersion { .. }));
+            assert_eq!(invalid, !valid, "{version}: {errors:?}");
+        }
+    }
+
     #[test]
     fn test_validate_allowed_tools_valid() {
         let mut metadata = base_metadata();
@@ -0,0 +1,206 @@
+//! Version bump enforcement against a git revision.
+//!
+//! Skills that declare `metadata.version` are consumed by pinning that version,
+//! so any change to `SKILL.md` or a bundled file must come with a version
+//! increase. [`check_version_bump`] compares a skill directory with its state
+//! at a git revision and reports changes that were made without one.
+
+use std::path::Path;
+use std::process::Command;
+
+use semver::Version;
+use serde_yaml::Value;
+
+use crate::error::ValidationError;
+use crate::formatting::parse_frontmatter;
+use crate::skill::SkillFile;
+
+/// Check that a skill's `metadata.version` increased if the skill changed since `rev`.
+///
+/// Only skills that declare `metadata.version` are checked, and a skill that
+/// did not exist at `rev` needs no bump. Returns an empty list when the skill
+/// is unchanged or its version was increased.
+pub fn check_version_bump(skill: &SkillFile, rev: &str) -> Vec<ValidationError> {
+    let git_error = |reason: String| {
+        vec![ValidationError::GitRevision {
+            rev: rev.to_string(),
+            reason,
+        }]
+    };
+
+    let dir = &skill.dir_path;
+    let commit = format!("{rev}^{{commit}}");
+    if let Err(reason) = git(dir, &["rev-parse", "--verify", "--quiet", &commit]) {
+        return git_error(if reason.is_empty() {
+            "unknown revision".to_string()
+        } else {
+            reason
+        });
+    }
+
+    let Some(current) = declared_version(&skill.content) else {
+        return Vec::new();
+    };
+
+    let Some(previous_content) = ["SKILL.md", "skill.md"]
+        .iter()
+        .find_map(|name| git(dir, &["show", &format!("{rev}:./{name}")]).ok())
+    else {
+        return Vec::new();
+    };
+
+    let changed = match skill_changed(dir, rev) {
+        Ok(changed) => changed,
+        Err(reason) => return git_error(reason),
+    };
+    if !changed {
+        return Vec::new();
+    }
+
+    let Some(previous) = declared_version(&previous_content) else {
+        return Vec::new();
+    };
+    let bumped = match (Version::parse(&previous), Version::parse(&current)) {
+        (Ok(previous), Ok(current)) => current > previous,
+        // An unparsable previous version cannot be compared; an invalid
+        // current version is reported by metadata validation.
+        (Err(_), Ok(_)) => true,
+        (_, Err(_)) => return Vec::new(),
+    };
+
+    if bumped {
+        Vec::new()
+    } else {
+        vec![ValidationError::VersionNotBumped {
+            rev: rev.to_string(),
+            previous,
+            current,
+        }]
+    }
+}
+
+/// Whether any tracked or untracked file in `dir` differs from `rev`.
+fn skill_changed(dir: &Path, rev: &str) -> Result<bool, String> {
+    let diff = git(dir, &["diff", "--name-only", rev, "--", "."])?;
+    if !diff.trim().is_empty() {
+        return Ok(true);
+    }
+    let untracked = git(
+        dir,
+        &["ls-files", "--others", "--exclude-standard", "--", "."],
+    )?;
+    Ok(!untracked.trim().is_empty())
+}
+
+fn declared_version(content: &str) -> Option<String> {
+    let (metadata, _) = parse_frontmatter(content).ok()?;
+    let Some(Value::Mapping(map)) = metadata.get("metadata") else {
+        return None;
+    };
+    match map.get("version") {
+        Some(Value::String(version)) => Some(version.trim().to_string()),
+        _ => None,
+    }
+}
+
+fn git(dir: &Path, args: &[&str]) -> Result<String, String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .map_err(|err| format!("failed to run git: {err}"))?;
+
+    if output.status.success() {
+        Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+    } else {
+        Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn run_git(dir: &Path, args: &[&str]) {
+        let status = Command::new("git")
+            .args(["-c", "user.name=test", "-c", "user.email=test@example.com"])
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .output()
+            .expect("run git")
+            .status;
+        assert!(status.success(), "git {args:?}");
+    }
+
+    /// A repository with one committed skill, returned with its directory.
+    fn repo(skill_md: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let skill_dir = dir.path().join("my-skill");
+        fs::create_dir_all(&skill_dir).expect("mkdir");
+        fs::write(skill_dir.join("SKILL.md"), skill_md).expect("write");
+        run_git(dir.path(), &["init", "-q"]);
+        run_git(dir.path(), &["add", "."]);
+        run_git(dir.path(), &["commit", "-q", "-m", "init"]);
+        (dir, skill_dir)
+    }
+
+    fn skill_md(version: Option<&str>, body: &str) -> String {
+        let metadata =
+            version.map_or_else(String::new, |v| format!("metadata:\n  version: \"{v}\"\n"));
+        format!("---\nname: my-skill\ndescription: A test skill\n{metadata}---\n{body}\n")
+    }
+
+    fn check(skill_dir: &Path, content: &str) -> Vec<ValidationError> {
+        fs::write(skill_dir.join("SKILL.md"), content).expect("write");
+        let skill = SkillFile {
+            dir_path: skill_dir.to_path_buf(),
+            file_path: skill_dir.join("SKILL.md"),
+            content: content.to_string(),
+        };
+        check_version_bump(&skill, "HEAD")
+    }
+
+    #[test]
+    fn skills_without_a_version_are_not_checked() {
+        let (_dir, skill_dir) = repo(&skill_md(None, "Body"));
+        assert!(check(&skill_dir, &skill_md(None, "Changed")).is_empty());
+    }
+
+    #[test]
+    fn requires_a_bump_when_the_skill_changed() {
+        let (_dir, skill_dir) = repo(&skill_md(Some("1.0.0"), "Body"));
+        assert!(check(&skill_dir, &skill_md(Some("1.0.0"), "Body")).is_empty());
+        assert_eq!(
+            check(&skill_dir, &skill_md(Some("1.0.0"), "Changed")),
+            [ValidationError::VersionNotBumped {
+                rev: "HEAD".to_string(),
+                previous: "1.0.0".to_string(),
+                current: "1.0.0".to_string(),
+            }]
+        );
+        assert!(check(&skill_dir, &skill_md(Some("1.1.0"), "Changed")).is_empty());
+    }
+
+    #[test]
+    fn adding_a_version_needs_no_bump() {
+        let (_dir, skill_dir) = repo(&skill_md(None, "Body"));
+        assert!(check(&skill_dir, &skill_md(Some("1.0.0"), "Changed")).is_empty());
+    }
+
+    #[test]
+    fn reports_unknown_revisions() {
+        let (_dir, skill_dir) = repo(&skill_md(Some("1.0.0"), "Body"));
+        let skill = SkillFile {
+            dir_path: skill_dir.clone(),
+            file_path: skill_dir.join("SKILL.md"),
+            content: skill_md(Some("1.0.0"), "Body"),
+        };
+        assert!(matches!(
+            check_version_bump(&skill, "no-such-rev").as_slice(),
+            [ValidationError::GitRevision { rev, .. }] if rev == "no-such-rev"
+        ));
+    }
+}